@@ -0,0 +1,147 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{transfer_from_escrow, unpack_token_account};
+use super::initialize_config::load_config;
+
+/// The client awards a posted job to a bid of its choice, at any time while bidding.
+pub fn accept_bid(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let job_account = &accounts[1];
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let job_data = job_account.try_borrow_data()?;
+    let job = state::JobAccount::try_from_slice(&job_data)?;
+    if job.client != *client.key {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    drop(job_data);
+
+    award_bid(accounts)
+}
+
+/// Assigns the job to the bid's resource at the bid price, refunds the escrow surplus
+/// over the bid payment to the client and moves the job to `Active`. A job whose deadline has
+/// passed can no longer be awarded and is left for `expire_job` to refund.
+///
+/// Accounts: `[signer, job, auction, bid, resource, escrow, escrow_vault, client_token_account,
+/// token_program, config, mint]`.
+pub(crate) fn award_bid(accounts: &[AccountInfo]) -> ProgramResult {
+    let job_account = &accounts[1];
+    let auction_account = &accounts[2];
+    let bid_account = &accounts[3];
+    let resource_account = &accounts[4];
+    let escrow_account = &accounts[5];
+    let vault_account = &accounts[6];
+    let client_token_account = &accounts[7];
+    let token_program = &accounts[8];
+    let config_account = &accounts[9];
+    let mint_account = &accounts[10];
+
+    let job_data = job_account.try_borrow_data()?;
+    let mut job = state::JobAccount::try_from_slice(&job_data)?;
+    drop(job_data);
+    if job.status != state::JobStatus::Pending {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    let now = Clock::get()?.unix_timestamp;
+    if now >= job.deadline {
+        return Err(ProgramError::Custom(38)); // JobDeadlinePassed
+    }
+
+    let auction_data = auction_account.try_borrow_data()?;
+    let auction = state::AuctionAccount::try_from_slice(&auction_data)?;
+    if auction.job != *job_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (bid_pda, _bid_bump) = Pubkey::create_program_address(
+        &[b"bid", job_account.key.as_ref(), resource_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if bid_account.key != &bid_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let bid_data = bid_account.try_borrow_data()?;
+    let bid = state::BidAccount::try_from_slice(&bid_data)?;
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if !resource.available_to(&job.client, now, job.deadline) {
+        return Err(ProgramError::Custom(43)); // ResourceLeased
+    }
+    let config = load_config(config_account)?;
+    if resource.host != bid.host || !job.requirements.is_satisfied_by(&resource, &config) {
+        return Err(ProgramError::Custom(34)); // RequirementsNotMet
+    }
+    if !resource.allocate(job.requirements.gpu_count, job.requirements.vram_gb()) {
+        return Err(ProgramError::Custom(33)); // InsufficientCapacity
+    }
+
+    let payment = job
+        .requirements
+        .payment_at(bid.price_per_hour)
+        .ok_or(ProgramError::Custom(1))?; // InvalidPrice
+
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let mut escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    drop(escrow_data);
+    if job.escrow_account != *escrow_account.key || escrow.status != state::EscrowStatus::Locked {
+        return Err(ProgramError::Custom(6)); // EscrowNotLocked
+    }
+    let surplus = escrow.amount.checked_sub(payment).ok_or(ProgramError::Custom(5))?; // InsufficientFunds
+
+    if surplus > 0 {
+        let client_token = unpack_token_account(client_token_account)?;
+        if client_token.owner.to_bytes() != job.client {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+
+        transfer_from_escrow(
+            accounts,
+            &escrow,
+            escrow_account,
+            vault_account,
+            mint_account,
+            client_token_account,
+            token_program,
+            surplus,
+        )?;
+    }
+
+    msg!("Awarded job to winning bid");
+    escrow.amount = payment;
+    escrow.host = bid.host;
+    let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
+    escrow_data_mut.copy_from_slice(&escrow.try_to_vec()?);
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    job.hosts[0] = state::HostAssignment {
+        host: bid.host,
+        resource: *resource_account.key,
+        payment_amount: payment,
+        ..state::HostAssignment::EMPTY
+    };
+    job.host_count = 1;
+    job.quorum = 1;
+    job.payment_amount = payment;
+    job.started_at = now;
+    job.status = state::JobStatus::Active;
+    let mut job_data_mut = job_account.try_borrow_mut_data()?;
+    job_data_mut.copy_from_slice(&job.try_to_vec()?);
+
+    Ok(())
+}
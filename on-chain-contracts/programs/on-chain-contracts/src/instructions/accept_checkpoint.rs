@@ -0,0 +1,66 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Client accepts a host's progress up to a checkpoint by proving that an intermediate output
+/// it received is included under the checkpoint's Merkle root. Accepted progress is paid for
+/// if the job later expires.
+///
+/// Data: `index (u16) | leaf ([u8; 32]) | proof ([u8; 32] each)`.
+pub fn accept_checkpoint(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let job_account = &accounts[1];
+    let checkpoint_account = &accounts[2];
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if data.len() < 34 || (data.len() - 34) % 32 != 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let index = u16::from_le_bytes(data[0..2].try_into().unwrap());
+    let leaf: [u8; 32] = data[2..34].try_into().unwrap();
+    let proof: Vec<[u8; 32]> = data[34..].chunks(32).map(|node| node.try_into().unwrap()).collect();
+
+    let job_data = job_account.try_borrow_data()?;
+    let job = state::JobAccount::try_from_slice(&job_data)?;
+    drop(job_data);
+    if job.client != *client.key {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if job.status != state::JobStatus::Active {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+
+    let log_data = checkpoint_account.try_borrow_data()?;
+    let mut log = state::CheckpointAccount::try_from_slice(&log_data)?;
+    drop(log_data);
+
+    let (checkpoint_pda, _bump) = Pubkey::create_program_address(
+        &[b"checkpoints", job_account.key.as_ref(), log.host.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if checkpoint_account.key != &checkpoint_pda || log.job != *job_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Acceptance only moves forward; an earlier checkpoint is already covered.
+    if index >= log.count || index < log.accepted {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if !log.checkpoints[index as usize].verify(&leaf, &proof) {
+        return Err(ProgramError::Custom(27)); // InvalidCheckpointProof
+    }
+
+    log.accepted = index + 1;
+    let mut log_data_mut = checkpoint_account.try_borrow_mut_data()?;
+    log_data_mut.copy_from_slice(&log.try_to_vec()?);
+
+    Ok(())
+}
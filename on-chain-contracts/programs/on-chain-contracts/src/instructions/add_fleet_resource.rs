@@ -0,0 +1,48 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Operator moves one of its resources into a fleet. The resource must have no stake of its
+/// own, delegated or not; from now on it is backed by the fleet's pooled stake.
+pub fn add_fleet_resource(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let operator = &accounts[0];
+    let fleet_account = &accounts[1];
+    let resource_account = &accounts[2];
+
+    if !operator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let fleet_data = fleet_account.try_borrow_data()?;
+    let mut fleet = state::FleetAccount::try_from_slice(&fleet_data)?;
+    drop(fleet_data);
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    if fleet.operator != *operator.key || resource.host != *operator.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+    if resource.fleet != Pubkey::default() || resource.staked_flux > 0 || resource.delegated_flux > 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if fleet.resource_count as usize == state::MAX_FLEET_RESOURCES {
+        return Err(ProgramError::Custom(49)); // FleetFull
+    }
+
+    fleet.resources[fleet.resource_count as usize] = *resource_account.key;
+    fleet.resource_count += 1;
+    let mut fleet_data_mut = fleet_account.try_borrow_mut_data()?;
+    fleet_data_mut.copy_from_slice(&fleet.try_to_vec()?);
+
+    resource.fleet = *fleet_account.key;
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
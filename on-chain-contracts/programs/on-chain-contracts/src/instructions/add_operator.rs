@@ -0,0 +1,73 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Host delegates a worker key to the resource until `expires_at`, or moves the expiry of an
+/// existing operator key.
+pub fn add_operator(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let operator: Pubkey = data[0..32].try_into().unwrap();
+    let expires_at = i64::from_le_bytes(data[32..40].try_into().unwrap());
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+    if operator == *host.key || expires_at <= Clock::get()?.unix_timestamp {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if let Some(i) = resource.operators().iter().position(|o| o.key == operator) {
+        resource.operators[i].expires_at = expires_at;
+    } else {
+        if resource.operator_count as usize == state::MAX_OPERATORS {
+            return Err(ProgramError::Custom(48)); // OperatorSlotsFull
+        }
+        resource.operators[resource.operator_count as usize] = state::OperatorKey { key: operator, expires_at };
+        resource.operator_count += 1;
+    }
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Resolves the assignment a signer acts for: its own as an assigned host, or that of the
+/// resource in `resource_account` if the signer is one of its operators.
+pub(crate) fn operated_assignment(
+    job: &state::JobAccount,
+    signer: &AccountInfo,
+    resource_account: Option<&AccountInfo>,
+) -> Result<usize, ProgramError> {
+    if let Some(index) = job.assignment_index(signer.key) {
+        return Ok(index);
+    }
+    let resource_account = resource_account.ok_or(ProgramError::Custom(3))?;
+    let index = job
+        .assignments()
+        .iter()
+        .position(|a| a.resource == *resource_account.key)
+        .ok_or(ProgramError::Custom(3))?;
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    if !resource.is_operator(signer.key, Clock::get()?.unix_timestamp) {
+        return Err(ProgramError::Custom(3));
+    }
+    Ok(index)
+}
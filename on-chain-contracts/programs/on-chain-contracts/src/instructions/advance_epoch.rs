@@ -0,0 +1,38 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Permissionless crank: closes the current epoch once its length has passed, crediting its
+/// emission to stakers by stake and to hosts by the GPU-hours they served in it.
+pub fn advance_epoch(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let _cranker = &accounts[0];
+    let reward_pool_account = &accounts[1];
+
+    let (pool_pda, _pool_bump) = Pubkey::create_program_address(&[b"rewards"], &pinocchio::program::ID)?;
+    if reward_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let pool_data = reward_pool_account.try_borrow_data()?;
+    let mut pool = state::RewardPool::try_from_slice(&pool_data)?;
+    drop(pool_data);
+
+    if Clock::get()?.unix_timestamp < pool.epoch_started_at + pool.epoch_length {
+        return Err(ProgramError::Custom(51)); // EpochNotOver
+    }
+
+    msg!("Closing reward epoch");
+    pool.advance();
+
+    let mut pool_data_mut = reward_pool_account.try_borrow_mut_data()?;
+    pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, instructions::Instructions, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+/// Records attester-measured specs on a resource. The transaction must carry an Ed25519
+/// program instruction, immediately before this one, verifying the attester's signature
+/// over the borsh-encoded `AttestationStatement` passed as instruction data.
+pub fn attest_resource(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let _payer = &accounts[0];
+    let resource_account = &accounts[1];
+    let attester_account = &accounts[2];
+    let instructions_sysvar = &accounts[3];
+
+    let statement = state::AttestationStatement::try_from_slice(data)?;
+    if statement.resource != *resource_account.key {
+        return Err(ProgramError::Custom(28)); // InvalidAttestation
+    }
+
+    let attester_data = attester_account.try_borrow_data()?;
+    let attester = state::AttesterAccount::try_from_slice(&attester_data)?;
+    let (attester_pda, _bump) = Pubkey::create_program_address(
+        &[b"attester", attester.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if attester_account.key != &attester_pda || !attester.active {
+        return Err(ProgramError::Custom(28)); // InvalidAttestation
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if statement.issued_at > now || now - statement.issued_at > state::AttestationStatement::MAX_AGE {
+        return Err(ProgramError::Custom(29)); // AttestationExpired
+    }
+
+    verify_ed25519_signature(instructions_sysvar, &attester.key, data)?;
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    let mut resource_mut = state::ResourceAccount::try_from_slice(&resource_data_mut)?;
+    resource_mut.attested = true;
+    resource_mut.attestation = state::AttestedSpecs {
+        gpu_model: statement.gpu_model,
+        vram_gb: statement.vram_gb,
+        cpu_cores: statement.cpu_cores,
+        compute_rating: statement.compute_rating,
+        attester: attester.key,
+        attested_at: now,
+    };
+    resource_data_mut.copy_from_slice(&resource_mut.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Checks that the instruction preceding the current one is an Ed25519 program
+/// instruction verifying a single signature by `signer` over exactly `message`.
+pub(crate) fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> ProgramResult {
+    let instructions = Instructions::try_from(instructions_sysvar)?;
+    let ed25519_ix = instructions.get_instruction_relative(-1)?;
+    if ed25519_ix.get_program_id() != &ED25519_PROGRAM_ID {
+        return Err(ProgramError::Custom(28)); // InvalidAttestation
+    }
+
+    // Layout: num_signatures (u8), padding (u8), then one 14-byte offsets record:
+    // signature offset/ix, public key offset/ix, message offset/size/ix (all u16).
+    let ix_data = ed25519_ix.get_instruction_data();
+    if ix_data.len() < 16 || ix_data[0] != 1 {
+        return Err(ProgramError::Custom(28)); // InvalidAttestation
+    }
+    let read_u16 = |at: usize| u16::from_le_bytes([ix_data[at], ix_data[at + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    // Every offset must point into the Ed25519 instruction itself.
+    if read_u16(4) != u16::MAX || read_u16(8) != u16::MAX || read_u16(14) != u16::MAX {
+        return Err(ProgramError::Custom(28)); // InvalidAttestation
+    }
+
+    let signed_key = ix_data.get(public_key_offset..public_key_offset + 32);
+    let signed_message = ix_data.get(message_offset..message_offset + message_size);
+    if signed_key != Some(signer.as_ref()) || signed_message != Some(message) {
+        return Err(ProgramError::Custom(28)); // InvalidAttestation
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::accept_bid::award_bid;
+
+/// Permissionless crank awarding a posted job to its best reputation-weighted bid
+/// once the bidding window has closed.
+pub fn award_job(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let auction_account = &accounts[2];
+    let bid_account = &accounts[3];
+
+    let auction_data = auction_account.try_borrow_data()?;
+    let auction = state::AuctionAccount::try_from_slice(&auction_data)?;
+    if Clock::get()?.unix_timestamp < auction.bidding_ends_at {
+        return Err(ProgramError::Custom(36)); // BiddingOpen
+    }
+    if auction.bid_count == 0 {
+        return Err(ProgramError::Custom(37)); // NoBids
+    }
+    if auction.best_bid != *bid_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    drop(auction_data);
+
+    award_bid(accounts)
+}
@@ -0,0 +1,111 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{transfer_from_escrow, unpack_token_account};
+use super::utils::transfer_lamports;
+
+/// Removes a resting order placed by the signer. Cancelling a bid also refunds its escrow and
+/// the prefunded job rent, for which `[escrow, escrow_vault, client_token_account,
+/// token_program, job, system_program, mint]` follow the book.
+pub fn cancel_order(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let owner = &accounts[0];
+    let book_account = &accounts[1];
+
+    let order_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let book_data = book_account.try_borrow_data()?;
+    let mut book = state::OrderBook::try_from_slice(&book_data)?;
+    drop(book_data);
+
+    if let Some(i) = book.asks().iter().position(|a| a.order_id == order_id && a.host == *owner.key) {
+        book.remove_ask(i);
+        msg!("Cancelled ask");
+    } else if let Some(i) = book.bids().iter().position(|b| b.order_id == order_id && b.client == *owner.key) {
+        let bid = book.bids[i];
+        book.remove_bid(i);
+        refund_bid_escrow(accounts, &bid)?;
+        msg!("Cancelled bid");
+    } else {
+        return Err(ProgramError::Custom(42)); // OrderNotFound
+    }
+
+    let mut book_data_mut = book_account.try_borrow_mut_data()?;
+    book_data_mut.copy_from_slice(&book.try_to_vec()?);
+
+    Ok(())
+}
+
+fn refund_bid_escrow(accounts: &[AccountInfo], bid: &state::BidOrder) -> ProgramResult {
+    let escrow_account = &accounts[2];
+    let vault_account = &accounts[3];
+    let client_token_account = &accounts[4];
+    let token_program = &accounts[5];
+    let job_account = &accounts[6];
+    let system_program = &accounts[7];
+    let mint_account = &accounts[8];
+
+    let (escrow_pda, _escrow_bump) = Pubkey::create_program_address(
+        &[b"escrow", bid.client.as_ref(), bid.job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if escrow_account.key != &escrow_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let mut escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    drop(escrow_data);
+    if escrow.status != state::EscrowStatus::Listed {
+        return Err(ProgramError::Custom(6)); // EscrowNotLocked
+    }
+
+    let client_token = unpack_token_account(client_token_account)?;
+    if client_token.owner.to_bytes() != bid.client {
+        return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+    }
+
+    transfer_from_escrow(
+        accounts,
+        &escrow,
+        escrow_account,
+        vault_account,
+        mint_account,
+        client_token_account,
+        token_program,
+        escrow.amount,
+    )?;
+
+    escrow.status = state::EscrowStatus::Refunded;
+    let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
+    escrow_data_mut.copy_from_slice(&escrow.try_to_vec()?);
+
+    let (job_pda, job_bump) = Pubkey::create_program_address(
+        &[b"job", bid.client.as_ref(), bid.job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if job_account.key != &job_pda || !job_account.data_is_empty() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let prefund = job_account.lamports();
+    if prefund > 0 {
+        transfer_lamports(
+            accounts,
+            job_account,
+            &accounts[0],
+            system_program,
+            prefund,
+            &[b"job", bid.client.as_ref(), bid.job_id.to_le_bytes().as_ref(), &[job_bump]],
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{token_balance, transfer_checked, unpack_token_account};
+use super::start_job::load_price_feed;
+
+/// Client of a failed job claims compensation in FLUX from the insurance pool, once per job.
+/// The payout is `insurance_cover_bps` of the payment for work that hosts slashed for vanishing
+/// never delivered, capped at the FLUX slashed from them, at `insurance_max_payout` and at the
+/// pool's balance. Jobs paid in another mint convert that payment to FLUX at the oracle prices,
+/// passing the job mint's and FLUX's price feeds after the FLUX mint.
+pub fn claim_insurance(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let job_account = &accounts[1];
+    let config_account = &accounts[2];
+    let insurance_account = &accounts[3];
+    let client_token_account = &accounts[4];
+    let token_program = &accounts[5];
+    let mint_account = &accounts[6];
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let account_data = job_account.try_borrow_data()?;
+    let mut job = state::JobAccount::try_from_slice(&account_data)?;
+    drop(account_data);
+
+    if job.client != *client.key {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if job.status != state::JobStatus::Failed {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    if job.insurance_claimed {
+        return Err(ProgramError::Custom(52)); // InsuranceClaimed
+    }
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+
+    let (insurance_pda, insurance_bump) = Pubkey::create_program_address(&[b"insurance"], &pinocchio::program::ID)?;
+    if insurance_account.key != &insurance_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if *mint_account.key != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+    let client_token = unpack_token_account(client_token_account)?;
+    if client_token.owner.to_bytes() != job.client || client_token.mint.to_bytes() != config.flux_mint {
+        return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+    }
+
+    let lost_flux = if job.mint == config.flux_mint {
+        job.insurable_loss
+    } else {
+        let now = Clock::get()?.unix_timestamp;
+        let mint_feed = load_price_feed(accounts.get(7).ok_or(ProgramError::NotEnoughAccountKeys)?, &job.mint, now)?;
+        let flux_feed = load_price_feed(accounts.get(8).ok_or(ProgramError::NotEnoughAccountKeys)?, &config.flux_mint, now)?;
+        mint_feed
+            .tokens_to_usd(job.insurable_loss)
+            .and_then(|usd| flux_feed.usd_to_tokens(usd))
+            .ok_or(ProgramError::Custom(1))? // InvalidPrice
+    };
+    let balance = token_balance(insurance_account)?;
+    let payout = ((lost_flux as u128 * config.insurance_cover_bps as u128 / 10_000) as u64)
+        .min(job.slashed_flux)
+        .min(config.insurance_max_payout)
+        .min(balance);
+    if payout == 0 {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    transfer_checked(
+        accounts,
+        token_program,
+        insurance_account,
+        mint_account,
+        client_token_account,
+        insurance_account,
+        payout,
+        &[&[b"insurance", &[insurance_bump]]],
+    )?;
+
+    msg!("Paid insurance for failed job");
+    job.insurance_claimed = true;
+    let mut job_data_mut = job_account.try_borrow_mut_data()?;
+    job_data_mut.copy_from_slice(&job.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{transfer_checked, unpack_token_account};
+
+/// Permissionless crank paying the host every lease period elapsed since the last claim,
+/// whether or not the lessee ran jobs, out of the lease's token vault. The lease completes
+/// once its last period is paid.
+pub fn claim_lease(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let _cranker = &accounts[0];
+    let lease_account = &accounts[1];
+    let vault_account = &accounts[2];
+    let host_token_account = &accounts[3];
+    let token_program = &accounts[4];
+    let mint_account = &accounts[5];
+
+    let lease_data = lease_account.try_borrow_data()?;
+    let mut lease = state::LeaseAccount::try_from_slice(&lease_data)?;
+    drop(lease_data);
+    if lease.status != state::LeaseStatus::Active {
+        return Err(ProgramError::Custom(44)); // LeaseNotActive
+    }
+    check_lease_vault(lease_account, vault_account)?;
+
+    let host_token = unpack_token_account(host_token_account)?;
+    if host_token.owner.to_bytes() != lease.host {
+        return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+    }
+
+    let elapsed = lease.periods_elapsed(Clock::get()?.unix_timestamp);
+    let amount = (elapsed - lease.claimed_periods) as u64 * lease.rate_per_period;
+    if amount > 0 {
+        let (_lease_pda, lease_bump) = Pubkey::create_program_address(
+            &[b"lease", lease.resource.as_ref(), lease.start_at.to_le_bytes().as_ref()],
+            &pinocchio::program::ID,
+        )?;
+
+        transfer_checked(
+            accounts,
+            token_program,
+            vault_account,
+            mint_account,
+            host_token_account,
+            lease_account,
+            amount,
+            &[&[b"lease", lease.resource.as_ref(), lease.start_at.to_le_bytes().as_ref(), &[lease_bump]]],
+        )?;
+    }
+
+    msg!("Paid elapsed lease periods to host");
+    lease.claimed_periods = elapsed;
+    if elapsed == lease.total_periods() {
+        lease.status = state::LeaseStatus::Completed;
+    }
+    let mut lease_data_mut = lease_account.try_borrow_mut_data()?;
+    lease_data_mut.copy_from_slice(&lease.try_to_vec()?);
+
+    Ok(())
+}
+
+pub(crate) fn check_lease_vault(lease_account: &AccountInfo, vault_account: &AccountInfo) -> ProgramResult {
+    let (vault_pda, _vault_bump) = Pubkey::create_program_address(
+        &[b"lease_vault", lease_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{transfer_checked, unpack_token_account};
+use super::initialize_rewards::check_rewards_vault;
+
+/// Host claims a resource's accrued epoch rewards. The delegators' part, split off as the
+/// rewards accrued, is compounded into their delegated stake first. Rewards are paid out of the
+/// pool's FLUX vault.
+pub fn claim_rewards(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+    let reward_pool_account = &accounts[2];
+    let host_token_account = &accounts[3];
+    let staking_account = &accounts[4];
+    let token_program = &accounts[5];
+    let vault_account = &accounts[6];
+    let mint_account = &accounts[7];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (pool_pda, pool_bump) = Pubkey::create_program_address(&[b"rewards"], &pinocchio::program::ID)?;
+    if reward_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_rewards_vault(vault_account)?;
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    let host_token = unpack_token_account(host_token_account)?;
+    if host_token.owner.to_bytes() != resource.host {
+        return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+    }
+
+    let pool_data = reward_pool_account.try_borrow_data()?;
+    let mut pool = state::RewardPool::try_from_slice(&pool_data)?;
+    drop(pool_data);
+
+    let now = Clock::get()?.unix_timestamp;
+    pool.sync_stake(&mut resource, now);
+    compound_delegator_rewards(
+        accounts,
+        resource_account,
+        &mut resource,
+        reward_pool_account,
+        staking_account,
+        token_program,
+        vault_account,
+        mint_account,
+        pool_bump,
+    )?;
+
+    let host_rewards = resource.pending_host_rewards;
+    if host_rewards > 0 {
+        transfer_checked(
+            accounts,
+            token_program,
+            vault_account,
+            mint_account,
+            host_token_account,
+            reward_pool_account,
+            host_rewards,
+            &[&[b"rewards", &[pool_bump]]],
+        )?;
+    }
+
+    msg!("Claimed epoch rewards");
+    resource.pending_host_rewards = 0;
+    // Compounded delegations raise the resource's stake weight from here on.
+    pool.sync_stake(&mut resource, now);
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    let mut pool_data_mut = reward_pool_account.try_borrow_mut_data()?;
+    pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Pays a resource's pending delegator rewards from the rewards vault into its staking account,
+/// compounding them into `delegated_flux`. With no delegation shares left to own them, they fall
+/// to the host instead. The caller re-weights the resource afterwards.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compound_delegator_rewards(
+    accounts: &[AccountInfo],
+    resource_account: &AccountInfo,
+    resource: &mut state::ResourceAccount,
+    reward_pool_account: &AccountInfo,
+    staking_account: &AccountInfo,
+    token_program: &AccountInfo,
+    vault_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    pool_bump: u8,
+) -> ProgramResult {
+    let rewards = resource.pending_delegator_rewards;
+    resource.pending_delegator_rewards = 0;
+    if rewards == 0 {
+        return Ok(());
+    }
+    if resource.delegation_shares == 0 {
+        resource.pending_host_rewards += rewards;
+        return Ok(());
+    }
+
+    let (staking_pda, _staking_bump) = Pubkey::create_program_address(
+        &[b"stake", resource_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // Only what reaches the staking account backs the delegations.
+    resource.delegated_flux += transfer_checked(
+        accounts,
+        token_program,
+        vault_account,
+        mint_account,
+        staking_account,
+        reward_pool_account,
+        rewards,
+        &[&[b"rewards", &[pool_bump]]],
+    )?;
+    Ok(())
+}
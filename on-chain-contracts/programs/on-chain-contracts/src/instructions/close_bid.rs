@@ -0,0 +1,51 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::utils::close_program_account;
+
+/// Host reclaims the rent of its bid once the auction is over: the job was awarded, failed or
+/// has been closed. Bids on a job still taking bids stay open.
+///
+/// Accounts: `[host, job, bid]`.
+pub fn close_bid(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let job_account = &accounts[1];
+    let bid_account = &accounts[2];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let bid_data = bid_account.try_borrow_data()?;
+    let bid = state::BidAccount::try_from_slice(&bid_data)?;
+    drop(bid_data);
+    if bid.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+    let (bid_pda, _bid_bump) = Pubkey::create_program_address(
+        &[b"bid", job_account.key.as_ref(), bid.resource.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if bid_account.key != &bid_pda || bid.job != *job_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !job_account.data_is_empty() {
+        let job_data = job_account.try_borrow_data()?;
+        let job = state::JobAccount::try_from_slice(&job_data)?;
+        if job.status == state::JobStatus::Pending {
+            return Err(ProgramError::Custom(4)); // InvalidJobStatus
+        }
+    }
+
+    close_program_account(bid_account, host)?;
+
+    msg!("Closed bid and returned its rent to the host");
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use spl_token_2022::extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions};
+
+use crate::state;
+use super::deposit_escrow::check_vault;
+use super::utils::close_program_account;
+
+/// Client closes a completed or failed job once its hosts' capacity has been released, its escrow
+/// has been released or refunded and the dispute window has passed, returning the rent of the job,
+/// escrow, escrow vault and auction accounts. Checkpoint logs are closed along with the job and
+/// their rent goes back to the hosts that opened them. Insurance must be claimed before closing,
+/// since the claim reads the job. Jobs funded by an organization return the rent to its credit
+/// account, which `withdraw_credit` pays out to the organization's admin.
+///
+/// Accounts: `[client, job, escrow, escrow_vault, token_program, mint, auction]`, then a
+/// `(checkpoint_log, host)` pair per assigned host in assignment order, then the organization's
+/// credit account for jobs it funded.
+pub fn close_job(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let job_account = &accounts[1];
+    let escrow_account = &accounts[2];
+    let vault_account = &accounts[3];
+    let token_program = &accounts[4];
+    let mint_account = &accounts[5];
+    let auction_account = &accounts[6];
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let job_data = job_account.try_borrow_data()?;
+    let job = state::JobAccount::try_from_slice(&job_data)?;
+    drop(job_data);
+
+    let (job_pda, _job_bump) = Pubkey::create_program_address(
+        &[b"job", client.key.as_ref(), job.job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if job_account.key != &job_pda || job.client != *client.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if job.escrow_account != *escrow_account.key || *mint_account.key != job.mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_vault(escrow_account, vault_account)?;
+    let (auction_pda, _auction_bump) = Pubkey::create_program_address(
+        &[b"auction", job_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if auction_account.key != &auction_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let log_count = 2 * job.host_count as usize;
+    if accounts.len() < 7 + log_count {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let log_accounts = &accounts[7..7 + log_count];
+
+    if !matches!(job.status, state::JobStatus::Completed | state::JobStatus::Failed) {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    // Closing a job that still holds slots would leave them allocated forever.
+    if !job.capacity_released {
+        return Err(ProgramError::Custom(59)); // CapacityNotReleased
+    }
+
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    drop(escrow_data);
+    if matches!(escrow.status, state::EscrowStatus::Locked | state::EscrowStatus::Listed) {
+        return Err(ProgramError::Custom(57)); // EscrowStillLocked
+    }
+    if Clock::get()?.unix_timestamp < job.closable_at() {
+        return Err(ProgramError::Custom(58)); // DisputeWindowOpen
+    }
+
+    let (_escrow_pda, escrow_bump) = Pubkey::create_program_address(
+        &[b"escrow", escrow.client.as_ref(), escrow.job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+
+    // An organization's job returns the rent to its credit account, passed last.
+    let rent_destination = if escrow.refund_owner == Pubkey::default() {
+        client
+    } else {
+        let credit_account = accounts.get(7 + log_count).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let (credit_pda, _credit_bump) = Pubkey::create_program_address(
+            &[b"credit", escrow.refund_owner.as_ref()],
+            &pinocchio::program::ID,
+        )?;
+        if credit_account.key != &credit_pda {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        credit_account
+    };
+
+    // A log left behind would be read as the checkpoints of a later job under the same ID.
+    for (assignment, pair) in job.assignments().iter().zip(log_accounts.chunks(2)) {
+        let (log_account, host_account) = (&pair[0], &pair[1]);
+        let (log_pda, _log_bump) = Pubkey::create_program_address(
+            &[b"checkpoints", job_account.key.as_ref(), assignment.host.as_ref()],
+            &pinocchio::program::ID,
+        )?;
+        if log_account.key != &log_pda || *host_account.key != assignment.host {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !log_account.data_is_empty() {
+            close_program_account(log_account, host_account)?;
+        }
+    }
+
+    close_token_vault(
+        accounts,
+        vault_account,
+        rent_destination,
+        escrow_account,
+        token_program,
+        mint_account,
+        &[b"escrow", escrow.client.as_ref(), escrow.job_id.to_le_bytes().as_ref(), &[escrow_bump]],
+    )?;
+
+    // Only posted jobs have an auction; their bids are reclaimed by the hosts with `close_bid`.
+    if !auction_account.data_is_empty() {
+        close_program_account(auction_account, client)?;
+    }
+    close_program_account(escrow_account, rent_destination)?;
+    close_program_account(job_account, rent_destination)?;
+
+    msg!("Closed job and returned its rent");
+    Ok(())
+}
+
+/// Closes an empty token vault owned by the `authority` PDA, sending its rent to `destination`.
+/// Token-2022 transfer fees withheld in the vault are harvested to the mint first, since the
+/// vault must hold nothing to close.
+pub(crate) fn close_token_vault(
+    accounts: &[AccountInfo],
+    vault_account: &AccountInfo,
+    destination: &AccountInfo,
+    authority: &AccountInfo,
+    token_program: &AccountInfo,
+    mint_account: &AccountInfo,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let vault_data = vault_account.try_borrow_data()?;
+    let withheld = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&vault_data)?
+        .get_extension::<TransferFeeAmount>()
+        .map_or(0, |fee| u64::from(fee.withheld_amount));
+    drop(vault_data);
+    if withheld > 0 {
+        let harvest_ix = spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+            token_program.key,
+            mint_account.key,
+            &[vault_account.key],
+        )?;
+
+        let harvest_instruction = Instruction {
+            program_id: *token_program.key,
+            accounts: vec![
+                AccountMeta::new(*mint_account.key, false),
+                AccountMeta::new(*vault_account.key, false),
+            ],
+            data: harvest_ix.data,
+        };
+
+        invoke_signed(&harvest_instruction, accounts, &[])?;
+    }
+
+    let close_ix = spl_token_2022::instruction::close_account(
+        token_program.key,
+        vault_account.key,
+        destination.key,
+        authority.key,
+        &[],
+    )?;
+
+    let close_instruction = Instruction {
+        program_id: *token_program.key,
+        accounts: vec![
+            AccountMeta::new(*vault_account.key, false),
+            AccountMeta::new(*destination.key, false),
+            AccountMeta::new_readonly(*authority.key, true),
+        ],
+        data: close_ix.data,
+    };
+
+    invoke_signed(&close_instruction, accounts, &[signer_seeds])
+}
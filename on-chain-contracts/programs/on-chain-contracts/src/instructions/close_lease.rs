@@ -0,0 +1,58 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::claim_lease::check_lease_vault;
+use super::close_job::close_token_vault;
+use super::utils::close_program_account;
+
+/// Lessee reclaims the rent of a lease and its token vault once the lease has completed or been
+/// terminated, which leaves the vault empty.
+///
+/// Accounts: `[client, lease, lease_vault, token_program, mint]`.
+pub fn close_lease(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let lease_account = &accounts[1];
+    let vault_account = &accounts[2];
+    let token_program = &accounts[3];
+    let mint_account = &accounts[4];
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let lease_data = lease_account.try_borrow_data()?;
+    let lease = state::LeaseAccount::try_from_slice(&lease_data)?;
+    drop(lease_data);
+
+    let (lease_pda, lease_bump) = Pubkey::create_program_address(
+        &[b"lease", lease.resource.as_ref(), lease.start_at.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if lease_account.key != &lease_pda || lease.client != *client.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_lease_vault(lease_account, vault_account)?;
+    if lease.status == state::LeaseStatus::Active {
+        return Err(ProgramError::Custom(60)); // LeaseStillActive
+    }
+
+    close_token_vault(
+        accounts,
+        vault_account,
+        client,
+        lease_account,
+        token_program,
+        mint_account,
+        &[b"lease", lease.resource.as_ref(), lease.start_at.to_le_bytes().as_ref(), &[lease_bump]],
+    )?;
+    close_program_account(lease_account, client)?;
+
+    msg!("Closed lease and returned its rent to the client");
+    Ok(())
+}
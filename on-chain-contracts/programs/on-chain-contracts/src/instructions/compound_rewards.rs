@@ -0,0 +1,65 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::claim_rewards::compound_delegator_rewards;
+use super::initialize_rewards::check_rewards_vault;
+
+/// Permissionless crank compounding a resource's pending delegator rewards into its delegated
+/// stake, so that delegators do not wait on the host to claim.
+///
+/// Accounts: `[cranker, resource, reward_pool, staking_account, token_program, rewards_vault,
+/// flux_mint]`.
+pub fn compound_rewards(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let _cranker = &accounts[0];
+    let resource_account = &accounts[1];
+    let reward_pool_account = &accounts[2];
+    let staking_account = &accounts[3];
+    let token_program = &accounts[4];
+    let vault_account = &accounts[5];
+    let mint_account = &accounts[6];
+
+    let (pool_pda, pool_bump) = Pubkey::create_program_address(&[b"rewards"], &pinocchio::program::ID)?;
+    if reward_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_rewards_vault(vault_account)?;
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    let pool_data = reward_pool_account.try_borrow_data()?;
+    let mut pool = state::RewardPool::try_from_slice(&pool_data)?;
+    drop(pool_data);
+
+    let now = Clock::get()?.unix_timestamp;
+    pool.sync_stake(&mut resource, now);
+    compound_delegator_rewards(
+        accounts,
+        resource_account,
+        &mut resource,
+        reward_pool_account,
+        staking_account,
+        token_program,
+        vault_account,
+        mint_account,
+        pool_bump,
+    )?;
+
+    msg!("Compounded delegator rewards");
+    pool.sync_stake(&mut resource, now);
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    let mut pool_data_mut = reward_pool_account.try_borrow_mut_data()?;
+    pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
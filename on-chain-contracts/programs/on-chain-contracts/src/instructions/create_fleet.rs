@@ -0,0 +1,84 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::create_token_vault;
+use super::initialize_config::load_config;
+use super::utils::create_pda_account;
+
+/// Creates an empty fleet for the signing operator, with its pooled stake vault at
+/// `[b"stake", fleet]`, a FLUX token account owned by the vault PDA itself.
+pub fn create_fleet(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let operator = &accounts[0];
+    let fleet_account = &accounts[1];
+    let system_program = &accounts[2];
+    let staking_account = &accounts[3];
+    let token_program = &accounts[4];
+    let config_account = &accounts[5];
+    let mint_account = &accounts[6];
+
+    if !operator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let fleet_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let (fleet_pda, bump) = Pubkey::create_program_address(
+        &[b"fleet", operator.key.as_ref(), fleet_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if fleet_account.key != &fleet_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (staking_pda, staking_bump) = Pubkey::create_program_address(
+        &[b"stake", fleet_pda.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let config = load_config(config_account)?;
+    if *mint_account.key != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    create_pda_account(
+        accounts,
+        operator,
+        fleet_account,
+        system_program,
+        state::FleetAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"fleet", operator.key.as_ref(), fleet_id.to_le_bytes().as_ref(), &[bump]],
+    )?;
+
+    create_token_vault(
+        accounts,
+        operator,
+        staking_account,
+        mint_account,
+        token_program,
+        system_program,
+        &staking_pda,
+        &[b"stake", fleet_pda.as_ref(), &[staking_bump]],
+    )?;
+
+    let fleet = state::FleetAccount {
+        operator: *operator.key,
+        fleet_id,
+        reputation_score: 0,
+        resource_count: 0,
+        resources: [Pubkey::default(); state::MAX_FLEET_RESOURCES],
+        unbonding_amount: 0,
+        unbonding_ends_at: 0,
+    };
+    let mut fleet_data = fleet_account.try_borrow_mut_data()?;
+    fleet_data.copy_from_slice(&fleet.try_to_vec()?);
+
+    Ok(())
+}
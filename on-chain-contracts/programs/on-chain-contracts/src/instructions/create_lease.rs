@@ -0,0 +1,149 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{create_token_vault, transfer_checked};
+use super::initialize_config::load_config;
+use super::utils::create_pda_account;
+
+/// Reserves a whole resource for the client at terms both parties sign. The client prepays
+/// every period into the lease's token vault, a token account of the client's mint owned by
+/// the lease PDA, and the resource only accepts the lessee's jobs while the lease runs.
+pub fn create_lease(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let host = &accounts[1];
+    let resource_account = &accounts[2];
+    let lease_account = &accounts[3];
+    let vault_account = &accounts[4];
+    let client_token_account = &accounts[5];
+    let token_program = &accounts[6];
+    let system_program = &accounts[7];
+    let mint_account = &accounts[8];
+    let config_account = &accounts[9];
+
+    let start_at = i64::from_le_bytes(data[0..8].try_into().unwrap());
+    let end_at = i64::from_le_bytes(data[8..16].try_into().unwrap());
+    let period_seconds = i64::from_le_bytes(data[16..24].try_into().unwrap());
+    let rate_per_period = u64::from_le_bytes(data[24..32].try_into().unwrap());
+    let client_penalty_bps = u16::from_le_bytes(data[32..34].try_into().unwrap());
+    let host_penalty_bps = u16::from_le_bytes(data[34..36].try_into().unwrap());
+
+    if !client.is_signer || !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if start_at < now
+        || end_at <= start_at
+        || period_seconds <= 0
+        || (end_at - start_at) % period_seconds != 0
+        || client_penalty_bps > 10_000
+        || host_penalty_bps > 10_000
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if rate_per_period == 0 {
+        return Err(ProgramError::Custom(1)); // InvalidPrice
+    }
+    if !load_config(config_account)?.accepts_mint(mint_account.key) {
+        return Err(ProgramError::Custom(53)); // MintNotAllowed
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+    // One lease at a time, current or upcoming.
+    if resource.lease_end > now {
+        return Err(ProgramError::Custom(43)); // ResourceLeased
+    }
+
+    let mut lease = state::LeaseAccount {
+        resource: *resource_account.key,
+        host: *host.key,
+        client: *client.key,
+        start_at,
+        end_at,
+        period_seconds,
+        rate_per_period,
+        deposit: 0,
+        claimed_periods: 0,
+        client_penalty_bps,
+        host_penalty_bps,
+        status: state::LeaseStatus::Active,
+    };
+    lease.deposit = rate_per_period
+        .checked_mul(lease.total_periods() as u64)
+        .ok_or(ProgramError::Custom(1))?; // InvalidPrice
+
+    let (lease_pda, bump) = Pubkey::create_program_address(
+        &[b"lease", resource_account.key.as_ref(), start_at.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if lease_account.key != &lease_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (vault_pda, vault_bump) = Pubkey::create_program_address(
+        &[b"lease_vault", lease_pda.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        client,
+        lease_account,
+        system_program,
+        state::LeaseAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"lease", resource_account.key.as_ref(), start_at.to_le_bytes().as_ref(), &[bump]],
+    )?;
+
+    create_token_vault(
+        accounts,
+        client,
+        vault_account,
+        mint_account,
+        token_program,
+        system_program,
+        &lease_pda,
+        &[b"lease_vault", lease_pda.as_ref(), &[vault_bump]],
+    )?;
+
+    // Every period is paid out of the deposit, so the vault must receive it in full.
+    let received = transfer_checked(
+        accounts,
+        token_program,
+        client_token_account,
+        mint_account,
+        vault_account,
+        client,
+        lease.deposit,
+        &[],
+    )?;
+    if received != lease.deposit {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    msg!("Leased resource");
+    let mut lease_data = lease_account.try_borrow_mut_data()?;
+    lease_data.copy_from_slice(&lease.try_to_vec()?);
+
+    resource.lessee = *client.key;
+    resource.lease_start = start_at;
+    resource.lease_end = end_at;
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::utils::create_pda_account;
+
+/// Opens an empty order book for a GPU class. Anyone may open a book for a class that has none.
+pub fn create_order_book(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let payer = &accounts[0];
+    let book_account = &accounts[1];
+    let system_program = &accounts[2];
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let gpu_class = core::str::from_utf8(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    if gpu_class.is_empty() || gpu_class.len() > 20 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (book_pda, bump) = Pubkey::create_program_address(
+        &[b"book", gpu_class.as_bytes()],
+        &pinocchio::program::ID,
+    )?;
+    if book_account.key != &book_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        payer,
+        book_account,
+        system_program,
+        state::OrderBook::SPACE,
+        &pinocchio::program::ID,
+        &[b"book", gpu_class.as_bytes(), &[bump]],
+    )?;
+
+    let book = state::OrderBook {
+        gpu_class: gpu_class.to_string(),
+        next_order_id: 0,
+        ask_count: 0,
+        asks: [state::AskOrder::EMPTY; state::MAX_ORDERS],
+        bid_count: 0,
+        bids: [state::BidOrder::EMPTY; state::MAX_ORDERS],
+    };
+    let mut book_data = book_account.try_borrow_mut_data()?;
+    book_data.copy_from_slice(&book.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::utils::create_pda_account;
+
+/// Creates an organization administered by the signer. Its credit vault is the credit account
+/// of the organization account and its token vault, topped up with `deposit_credit`.
+pub fn create_org(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let org_account = &accounts[1];
+    let system_program = &accounts[2];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let org_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let mut org = state::OrgAccount {
+        admin: *admin.key,
+        org_id,
+        period_seconds: 0,
+        budget: state::SpendBudget {
+            period_start: Clock::get()?.unix_timestamp,
+            ..Default::default()
+        },
+        allowed_host_count: 0,
+        allowed_hosts: [Pubkey::default(); state::MAX_ORG_HOSTS],
+    };
+    apply_org_settings(&mut org, &data[8..])?;
+
+    let (org_pda, bump) = Pubkey::create_program_address(
+        &[b"org", admin.key.as_ref(), org_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if org_account.key != &org_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        admin,
+        org_account,
+        system_program,
+        state::OrgAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"org", admin.key.as_ref(), org_id.to_le_bytes().as_ref(), &[bump]],
+    )?;
+
+    let mut org_data = org_account.try_borrow_mut_data()?;
+    org_data.copy_from_slice(&org.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Applies `period_seconds (i64) | period_limit (u64) | allowed_hosts (Vec<Pubkey>)`.
+pub(crate) fn apply_org_settings(org: &mut state::OrgAccount, data: &[u8]) -> ProgramResult {
+    let period_seconds = i64::from_le_bytes(data[0..8].try_into().unwrap());
+    let period_limit = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let allowed_hosts = Vec::<Pubkey>::try_from_slice(&data[16..])?;
+
+    if period_seconds <= 0 || allowed_hosts.len() > state::MAX_ORG_HOSTS {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    org.period_seconds = period_seconds;
+    org.budget.limit = period_limit;
+    org.allowed_host_count = allowed_hosts.len() as u8;
+    org.allowed_hosts = [Pubkey::default(); state::MAX_ORG_HOSTS];
+    org.allowed_hosts[..allowed_hosts.len()].copy_from_slice(&allowed_hosts);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::transfer_checked;
+use super::utils::create_pda_account;
+use super::sync_rewards::sync_pool_stake;
+
+/// Delegates FLUX to a resource's stake in exchange for delegation shares, creating the
+/// delegation record on first use. Fleet members are backed by their pool and take no delegations.
+/// Shares left over from a generation wiped out by slashing are discarded first.
+pub fn delegate_stake(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let delegator = &accounts[0];
+    let resource_account = &accounts[1];
+    let delegation_account = &accounts[2];
+    let token_account = &accounts[3];
+    let token_program = &accounts[4];
+    let system_program = &accounts[5];
+    let reward_pool_account = &accounts[6];
+    let staking_account = &accounts[7];
+    let mint_account = &accounts[8];
+
+    if !delegator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    if amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.fleet != Pubkey::default() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (delegation_pda, bump) = Pubkey::create_program_address(
+        &[b"delegation", resource_account.key.as_ref(), delegator.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if delegation_account.key != &delegation_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut delegation = if delegation_account.data_is_empty() {
+        create_pda_account(
+            accounts,
+            delegator,
+            delegation_account,
+            system_program,
+            state::DelegationAccount::SPACE,
+            &pinocchio::program::ID,
+            &[b"delegation", resource_account.key.as_ref(), delegator.key.as_ref(), &[bump]],
+        )?;
+
+        state::DelegationAccount {
+            resource: *resource_account.key,
+            delegator: *delegator.key,
+            shares: 0,
+            generation: resource.delegation_generation,
+            unbonding_shares: 0,
+            unbonding_generation: resource.unbonding_generation,
+            unbonding_ends_at: 0,
+        }
+    } else {
+        let delegation_data = delegation_account.try_borrow_data()?;
+        state::DelegationAccount::try_from_slice(&delegation_data)?
+    };
+
+    let (staking_pda, _staking_bump) = Pubkey::create_program_address(
+        &[b"stake", resource_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // Delegations are worth what the staking account received, net of any transfer fee.
+    let received = transfer_checked(
+        accounts,
+        token_program,
+        token_account,
+        mint_account,
+        staking_account,
+        delegator,
+        amount,
+        &[],
+    )?;
+
+    resource.void_wiped_delegations();
+    if delegation.generation != resource.delegation_generation {
+        delegation.shares = 0;
+        delegation.generation = resource.delegation_generation;
+    }
+
+    let shares = resource.shares_for(received);
+    msg!("Delegated FLUX");
+    delegation.shares += shares;
+    resource.delegation_shares += shares;
+    resource.delegated_flux += received;
+    sync_pool_stake(reward_pool_account, &mut resource)?;
+
+    let mut delegation_data_mut = delegation_account.try_borrow_mut_data()?;
+    delegation_data_mut.copy_from_slice(&delegation.try_to_vec()?);
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{create_token_vault, transfer_checked};
+use super::initialize_config::load_config;
+use super::utils::create_pda_account;
+
+/// Tops up a credit vault with FLUX, creating the credit account and its token vault on the
+/// first deposit. The vault belongs to the signer unless an owner, such as an organization
+/// account, follows the amount.
+pub fn deposit_credit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let credit_account = &accounts[1];
+    let vault_account = &accounts[2];
+    let token_account = &accounts[3];
+    let token_program = &accounts[4];
+    let system_program = &accounts[5];
+    let config_account = &accounts[6];
+    let mint_account = &accounts[7];
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let owner: Pubkey = if data.len() >= 40 { data[8..40].try_into().unwrap() } else { *client.key };
+
+    let config = load_config(config_account)?;
+    if *mint_account.key != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    let (credit_pda, bump) = Pubkey::create_program_address(
+        &[b"credit", owner.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if credit_account.key != &credit_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (vault_pda, vault_bump) = Pubkey::create_program_address(
+        &[b"credit_vault", credit_pda.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut credit = if credit_account.data_is_empty() {
+        create_pda_account(
+            accounts,
+            client,
+            credit_account,
+            system_program,
+            state::CreditAccount::SPACE,
+            &pinocchio::program::ID,
+            &[b"credit", owner.as_ref(), &[bump]],
+        )?;
+
+        create_token_vault(
+            accounts,
+            client,
+            vault_account,
+            mint_account,
+            token_program,
+            system_program,
+            &credit_pda,
+            &[b"credit_vault", credit_pda.as_ref(), &[vault_bump]],
+        )?;
+
+        state::CreditAccount {
+            client: owner,
+            balance: 0,
+        }
+    } else {
+        let credit_data = credit_account.try_borrow_data()?;
+        state::CreditAccount::try_from_slice(&credit_data)?
+    };
+
+    let received = transfer_checked(
+        accounts,
+        token_program,
+        token_account,
+        mint_account,
+        vault_account,
+        client,
+        amount,
+        &[],
+    )?;
+
+    msg!("Deposited FLUX to credit");
+    credit.balance += received;
+    let mut credit_data_mut = credit_account.try_borrow_mut_data()?;
+    credit_data_mut.copy_from_slice(&credit.try_to_vec()?);
+
+    Ok(())
+}
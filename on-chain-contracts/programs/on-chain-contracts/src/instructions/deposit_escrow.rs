@@ -2,21 +2,32 @@ use pinocchio::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction},
     msg,
-    program::invoke,
+    program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
     ProgramResult,
 };
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
 
 use crate::state;
+use super::utils::create_pda_account;
 
 const SPL_TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
+/// Locks tokens in the escrow for `job_id`, opening the escrow on the first deposit and
+/// topping it up afterwards. The escrow takes the mint of the client's token account, which
+/// must be FLUX or another mint accepted by the config. The locked amount is what the vault
+/// actually received, net of any Token-2022 transfer fee.
 pub fn deposit_escrow(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let client = &accounts[0];
     let escrow_account = &accounts[1];
-    let token_account = &accounts[2];
-    let token_program = &accounts[3];
+    let vault_account = &accounts[2];
+    let token_account = &accounts[3];
+    let token_program = &accounts[4];
+    let config_account = &accounts[5];
+    let mint_account = &accounts[6];
+    let system_program = &accounts[7];
 
     if !client.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
@@ -25,47 +36,288 @@ pub fn deposit_escrow(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
     let job_id = u64::from_le_bytes(data[8..16].try_into().unwrap());
 
-    let (escrow_pda, bump) = Pubkey::create_program_address(
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+
+    let token_data = token_account.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_data)?.base.mint.to_bytes();
+    drop(token_data);
+    if !config.accepts_mint(&mint) {
+        return Err(ProgramError::Custom(53)); // MintNotAllowed
+    }
+    if *mint_account.key != mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    let mut escrow = if escrow_account.data_is_empty() {
+        open_escrow(accounts, client, escrow_account, vault_account, mint_account, token_program, system_program, job_id)?;
+        state::EscrowAccount {
+            job_id,
+            client: *client.key,
+            host: Pubkey::default(),
+            amount: 0,
+            status: state::EscrowStatus::Locked,
+            mint,
+            refund_owner: Pubkey::default(),
+        }
+    } else {
+        let (escrow_pda, _escrow_bump) = Pubkey::create_program_address(
+            &[b"escrow", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
+            &pinocchio::program::ID,
+        )?;
+        if escrow_account.key != &escrow_pda {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let escrow_data = escrow_account.try_borrow_data()?;
+        let escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+        drop(escrow_data);
+        if escrow.client != *client.key || escrow.job_id != job_id {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if escrow.status != state::EscrowStatus::Locked {
+            return Err(ProgramError::Custom(6)); // EscrowNotLocked
+        }
+        if escrow.mint != mint {
+            return Err(ProgramError::Custom(54)); // MintMismatch
+        }
+        escrow
+    };
+    check_vault(escrow_account, vault_account)?;
+
+    let received = transfer_checked(
+        accounts,
+        token_program,
+        token_account,
+        mint_account,
+        vault_account,
+        client,
+        amount,
+        &[],
+    )?;
+
+    msg!("Deposited tokens to escrow");
+    escrow.amount += received;
+
+    let mut account_data = escrow_account.try_borrow_mut_data()?;
+    account_data.copy_from_slice(&escrow.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Creates the escrow state PDA for `job_id` of the payer and its token vault PDA, a token
+/// account of `mint_account` whose authority is the escrow PDA.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_escrow(
+    accounts: &[AccountInfo],
+    client: &AccountInfo,
+    escrow_account: &AccountInfo,
+    vault_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    token_program: &AccountInfo,
+    system_program: &AccountInfo,
+    job_id: u64,
+) -> ProgramResult {
+    if *token_program.key != SPL_TOKEN_PROGRAM_ID && *token_program.key != TOKEN_2022_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (escrow_pda, escrow_bump) = Pubkey::create_program_address(
         &[b"escrow", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
         &pinocchio::program::ID,
     )?;
-
     if escrow_account.key != &escrow_pda {
         return Err(ProgramError::InvalidAccountData);
     }
+    let (vault_pda, vault_bump) = Pubkey::create_program_address(
+        &[b"escrow_vault", escrow_pda.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        client,
+        escrow_account,
+        system_program,
+        state::EscrowAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"escrow", client.key.as_ref(), job_id.to_le_bytes().as_ref(), &[escrow_bump]],
+    )?;
 
-    let transfer_ix = spl_token::instruction::transfer(
-        &SPL_TOKEN_PROGRAM_ID,
-        token_account.key,
+    create_token_vault(
+        accounts,
+        client,
+        vault_account,
+        mint_account,
+        token_program,
+        system_program,
         &escrow_pda,
-        client.key,
-        &[],
-        amount,
+        &[b"escrow_vault", escrow_pda.as_ref(), &[vault_bump]],
+    )
+}
+
+/// Creates a token account for `mint_account` at the PDA given by `vault_seeds`, with
+/// `authority` as its owner.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_token_vault(
+    accounts: &[AccountInfo],
+    payer: &AccountInfo,
+    vault_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    token_program: &AccountInfo,
+    system_program: &AccountInfo,
+    authority: &Pubkey,
+    vault_seeds: &[&[u8]],
+) -> ProgramResult {
+    if *token_program.key != SPL_TOKEN_PROGRAM_ID && *token_program.key != TOKEN_2022_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Token-2022 mints may require extensions, such as the transfer fee amount, on every account.
+    let mint_data = mint_account.try_borrow_data()?;
+    let mint_extensions = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?.get_extension_types()?;
+    drop(mint_data);
+    let account_extensions = ExtensionType::get_required_init_account_extensions(&mint_extensions);
+    let vault_space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&account_extensions)?;
+
+    create_pda_account(
+        accounts,
+        payer,
+        vault_account,
+        system_program,
+        vault_space,
+        token_program.key,
+        vault_seeds,
     )?;
 
-    let transfer_instruction = Instruction {
+    let init_vault_ix = spl_token_2022::instruction::initialize_account3(
+        token_program.key,
+        vault_account.key,
+        mint_account.key,
+        authority,
+    )?;
+
+    let init_vault_instruction = Instruction {
         program_id: *token_program.key,
         accounts: vec![
-            AccountMeta::new(*token_account.key, false),
-            AccountMeta::new(escrow_pda, false),
-            AccountMeta::new(*client.key, true),
+            AccountMeta::new(*vault_account.key, false),
+            AccountMeta::new_readonly(*mint_account.key, false),
         ],
-        data: transfer_ix.data,
+        data: init_vault_ix.data,
     };
 
-    invoke(&transfer_instruction, accounts)?;
+    invoke_signed(&init_vault_instruction, accounts, &[])
+}
 
-    msg!("Deposited {} FLUX to escrow for job {}", amount, job_id);
-    let mut escrow_data = state::EscrowAccount {
-        job_id,
-        client: *client.key,
-        host: Pubkey::default(),
-        amount,
-        status: state::EscrowStatus::Locked,
-    };
+pub(crate) fn check_vault(escrow_account: &AccountInfo, vault_account: &AccountInfo) -> ProgramResult {
+    let (vault_pda, _vault_bump) = Pubkey::create_program_address(
+        &[b"escrow_vault", escrow_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
 
-    let mut account_data = escrow_account.try_borrow_mut_data()?;
-    account_data.copy_from_slice(&escrow_data.try_to_vec()?);
+/// Pays `amount` out of an escrow's token vault, signed by the escrow PDA. `mint_account` must
+/// be the escrow's mint, whose decimals `transfer_checked` needs.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn transfer_from_escrow(
+    accounts: &[AccountInfo],
+    escrow: &state::EscrowAccount,
+    escrow_account: &AccountInfo,
+    vault_account: &AccountInfo,
+    mint_account: &AccountInfo,
+    destination: &AccountInfo,
+    token_program: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    check_vault(escrow_account, vault_account)?;
+    if *mint_account.key != escrow.mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+    let (_escrow_pda, escrow_bump) = Pubkey::create_program_address(
+        &[b"escrow", escrow.client.as_ref(), escrow.job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
 
+    transfer_checked(
+        accounts,
+        token_program,
+        vault_account,
+        mint_account,
+        destination,
+        escrow_account,
+        amount,
+        &[&[b"escrow", escrow.client.as_ref(), escrow.job_id.to_le_bytes().as_ref(), &[escrow_bump]]],
+    )?;
     Ok(())
 }
+
+/// Base state of a token account owned by either token program, ignoring its extensions.
+pub(crate) fn unpack_token_account(token_account: &AccountInfo) -> Result<spl_token_2022::state::Account, ProgramError> {
+    let data = token_account.try_borrow_data()?;
+    Ok(StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?.base)
+}
+
+/// Token balance of an account owned by either token program.
+pub(crate) fn token_balance(token_account: &AccountInfo) -> Result<u64, ProgramError> {
+    Ok(unpack_token_account(token_account)?.amount)
+}
+
+/// Moves `amount` with `transfer_checked` through the classic token program or Token-2022,
+/// signing with `signer_seeds` when the authority is a PDA. Returns what the destination
+/// actually received, which is less than `amount` for mints with a transfer fee.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn transfer_checked(
+    accounts: &[AccountInfo],
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    mint_account: &AccountInfo,
+    destination: &AccountInfo,
+    authority: &AccountInfo,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64, ProgramError> {
+    if *token_program.key != SPL_TOKEN_PROGRAM_ID && *token_program.key != TOKEN_2022_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mint_data = mint_account.try_borrow_data()?;
+    let decimals = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?.base.decimals;
+    drop(mint_data);
+
+    let transfer_ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        source.key,
+        mint_account.key,
+        destination.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+
+    let transfer_instruction = Instruction {
+        program_id: *token_program.key,
+        accounts: vec![
+            AccountMeta::new(*source.key, false),
+            AccountMeta::new_readonly(*mint_account.key, false),
+            AccountMeta::new(*destination.key, false),
+            AccountMeta::new_readonly(*authority.key, true),
+        ],
+        data: transfer_ix.data,
+    };
+
+    let balance_before = token_balance(destination)?;
+    invoke_signed(&transfer_instruction, accounts, signer_seeds)?;
+    Ok(token_balance(destination)? - balance_before)
+}
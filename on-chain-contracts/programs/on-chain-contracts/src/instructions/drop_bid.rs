@@ -0,0 +1,101 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::initialize_config::load_config;
+use super::utils::close_program_account;
+
+/// Permissionless crank removing an auction's best bid once it can no longer be awarded, because
+/// its resource went offline, ran out of capacity, was leased out or stopped meeting the job's
+/// requirements. The bid's rent goes to the cranker. Every other live bid of the auction must be
+/// passed after the config, so the next best one can take its place.
+///
+/// Accounts: `[cranker, job, auction, bid, resource, config, ...other_bids]`.
+pub fn drop_bid(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let cranker = &accounts[0];
+    let job_account = &accounts[1];
+    let auction_account = &accounts[2];
+    let bid_account = &accounts[3];
+    let resource_account = &accounts[4];
+    let config_account = &accounts[5];
+    let other_bids = &accounts[6..];
+
+    if !cranker.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let job_data = job_account.try_borrow_data()?;
+    let job = state::JobAccount::try_from_slice(&job_data)?;
+    drop(job_data);
+    if job.status != state::JobStatus::Pending {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+
+    let auction_data = auction_account.try_borrow_data()?;
+    let mut auction = state::AuctionAccount::try_from_slice(&auction_data)?;
+    drop(auction_data);
+    if auction.job != *job_account.key || auction.best_bid != *bid_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let bid_data = bid_account.try_borrow_data()?;
+    let bid = state::BidAccount::try_from_slice(&bid_data)?;
+    drop(bid_data);
+    if bid.resource != *resource_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Same checks `award_bid` makes, run against a copy of the resource.
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    let config = load_config(config_account)?;
+    let awardable = resource.available_to(&job.client, Clock::get()?.unix_timestamp, job.deadline)
+        && resource.host == bid.host
+        && job.requirements.is_satisfied_by(&resource, &config)
+        && resource.allocate(job.requirements.gpu_count, job.requirements.vram_gb());
+    if awardable {
+        return Err(ProgramError::Custom(39)); // BidStillAwardable
+    }
+
+    // The remaining bids must all be present, so the lowest score among them is the new best.
+    auction.bid_count -= 1;
+    if other_bids.len() != auction.bid_count as usize {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    auction.best_bid = Pubkey::default();
+    auction.best_score = u64::MAX;
+    for (i, other_account) in other_bids.iter().enumerate() {
+        if other_account.key == bid_account.key || other_bids[..i].iter().any(|o| o.key == other_account.key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let other_data = other_account.try_borrow_data()?;
+        let other = state::BidAccount::try_from_slice(&other_data)?;
+        let (other_pda, _bump) = Pubkey::create_program_address(
+            &[b"bid", job_account.key.as_ref(), other.resource.as_ref()],
+            &pinocchio::program::ID,
+        )?;
+        if other_account.key != &other_pda {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if other.score < auction.best_score {
+            auction.best_score = other.score;
+            auction.best_bid = *other_account.key;
+        }
+    }
+
+    let mut auction_data_mut = auction_account.try_borrow_mut_data()?;
+    auction_data_mut.copy_from_slice(&auction.try_to_vec()?);
+    drop(auction_data_mut);
+
+    close_program_account(bid_account, cranker)?;
+
+    msg!("Dropped a bid that can no longer be awarded");
+    Ok(())
+}
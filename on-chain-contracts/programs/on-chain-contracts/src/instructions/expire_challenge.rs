@@ -0,0 +1,55 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Permissionless crank closing a challenge that ran out of time. A host that let the
+/// challenge run past its deadline is penalized; an answered challenge that no attester
+/// settled before its verdict deadline is closed as `Unverified` without penalty.
+pub fn expire_challenge(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let _cranker = &accounts[0];
+    let resource_account = &accounts[1];
+    let challenge_account = &accounts[2];
+
+    let challenge_data = challenge_account.try_borrow_data()?;
+    let mut challenge = state::ChallengeAccount::try_from_slice(&challenge_data)?;
+    drop(challenge_data);
+    if challenge.resource != *resource_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    match challenge.status {
+        state::ChallengeStatus::Issued => {
+            if now <= challenge.deadline {
+                return Err(ProgramError::Custom(31)); // ChallengeNotDue
+            }
+
+            let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+            let mut resource_mut = state::ResourceAccount::try_from_slice(&resource_data_mut)?;
+            resource_mut.reputation_score = resource_mut
+                .reputation_score
+                .saturating_sub(state::ChallengeAccount::REPUTATION_PENALTY);
+            resource_mut.attested = false;
+            resource_data_mut.copy_from_slice(&resource_mut.try_to_vec()?);
+
+            challenge.status = state::ChallengeStatus::TimedOut;
+        }
+        state::ChallengeStatus::Answered => {
+            if now <= challenge.verdict_deadline() {
+                return Err(ProgramError::Custom(31)); // ChallengeNotDue
+            }
+            challenge.status = state::ChallengeStatus::Unverified;
+        }
+        _ => return Err(ProgramError::InvalidAccountData),
+    }
+
+    let mut challenge_data_mut = challenge_account.try_borrow_mut_data()?;
+    challenge_data_mut.copy_from_slice(&challenge.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{transfer_checked, transfer_from_escrow, unpack_token_account};
+use super::initialize_insurance::check_insurance_pool;
+use super::stake_fleet::stake_pool;
+use super::sync_rewards::sync_pool_stake;
+use super::withdraw_credit::{check_refund_credit, credit_refunds};
+
+/// Reputation points removed from a host whose job runs past its deadline.
+pub const EXPIRY_REPUTATION_PENALTY: u16 = 100;
+/// Share of the host's stake (in basis points) slashed to the client on expiry.
+pub const EXPIRY_SLASH_BPS: u64 = 500;
+
+/// Permissionless crank: fails an overdue job, refunds the client and penalizes the hosts
+/// that never revealed a result. Hosts that revealed are marked for dispute instead. Slashed
+/// stake goes to the client's FLUX token account, less `insurance_slash_bps` paid into the
+/// insurance pool, whatever mint the job was paid in. Before the refund, each host is paid for
+/// the progress the client accepted with `accept_checkpoint`.
+///
+/// Accounts after the job's mint come in `(resource, staking_account, checkpoint_log,
+/// host_token_account)` groups, one per assigned host in assignment order. Fleet members pass
+/// their fleet's stake pool. Jobs funded by an organization pass its credit account last.
+pub fn expire_job(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let _cranker = &accounts[0];
+    let job_account = &accounts[1];
+    let escrow_account = &accounts[2];
+    let vault_account = &accounts[3];
+    let client_token_account = &accounts[4];
+    let token_program = &accounts[5];
+    let config_account = &accounts[6];
+    let insurance_account = &accounts[7];
+    let reward_pool_account = &accounts[8];
+    let client_flux_account = &accounts[9];
+    let flux_mint_account = &accounts[10];
+    let mint_account = &accounts[11];
+
+    let account_data = job_account.try_borrow_data()?;
+    let mut job = state::JobAccount::try_from_slice(&account_data)?;
+    drop(account_data);
+
+    // Posted jobs that were never awarded expire the same way, with no hosts to penalize.
+    if job.status != state::JobStatus::Active && job.status != state::JobStatus::Pending {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    // Hosts that committed in time keep the reveal window before the job can be expired.
+    let expires_at = if job.commit_count() > 0 {
+        job.deadline + state::JobAccount::REVEAL_WINDOW
+    } else {
+        job.deadline
+    };
+    if Clock::get()?.unix_timestamp <= expires_at {
+        return Err(ProgramError::Custom(8)); // JobNotExpired
+    }
+    // A quorum result is settled through `settle_job`, unless an audit has refuted it.
+    if job.quorum_hash().is_some() && job.audit_status != state::AuditStatus::Failed {
+        return Err(ProgramError::Custom(17)); // QuorumReached
+    }
+    if job.escrow_account != *escrow_account.key || accounts.len() < 12 + 4 * job.host_count as usize {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let host_accounts = &accounts[12..12 + 4 * job.host_count as usize];
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+
+    check_insurance_pool(insurance_account)?;
+
+    if *flux_mint_account.key != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let mut escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    drop(escrow_data);
+    if escrow.status != state::EscrowStatus::Locked {
+        return Err(ProgramError::Custom(6)); // EscrowNotLocked
+    }
+
+    // An organization's FLUX job pays refund and slashes back into its credit vault, passed as
+    // both client token accounts.
+    let refund_credit = if escrow.refund_owner == Pubkey::default() {
+        let client_token = unpack_token_account(client_token_account)?;
+        if client_token.owner.to_bytes() != job.client || client_token.mint.to_bytes() != job.mint {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+        // Slashes are in FLUX; on FLUX jobs this is the same account as the refund's.
+        let client_flux = unpack_token_account(client_flux_account)?;
+        if client_flux.owner.to_bytes() != job.client || client_flux.mint.to_bytes() != config.flux_mint {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+        None
+    } else {
+        let credit_account = accounts.get(12 + host_accounts.len()).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if client_flux_account.key != client_token_account.key {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+        Some((credit_account, check_refund_credit(&escrow.refund_owner, credit_account, client_token_account)?))
+    };
+
+    let mut checkpoint_paid = [0u64; state::MAX_HOSTS];
+    for (i, group) in host_accounts.chunks(4).enumerate() {
+        let (checkpoint_account, host_token_account) = (&group[2], &group[3]);
+        let payment = checkpoint_payment(&job, i, job_account, checkpoint_account)?;
+        if payment == 0 {
+            continue;
+        }
+        let host_token = unpack_token_account(host_token_account)?;
+        if host_token.owner.to_bytes() != job.hosts[i].host || host_token.mint.to_bytes() != job.mint {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+        transfer_from_escrow(accounts, &escrow, escrow_account, vault_account, mint_account, host_token_account, token_program, payment)?;
+        checkpoint_paid[i] = payment;
+    }
+
+    let refund = escrow.amount.checked_sub(checkpoint_paid.iter().sum()).ok_or(ProgramError::Custom(5))?; // InsufficientFunds
+    if refund > 0 {
+        transfer_from_escrow(accounts, &escrow, escrow_account, vault_account, mint_account, client_token_account, token_program, refund)?;
+    }
+
+    msg!("Refunded expired job escrow to client");
+    escrow.status = state::EscrowStatus::Refunded;
+    let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
+    escrow_data_mut.copy_from_slice(&escrow.try_to_vec()?);
+
+    // Slash part of each silent host's stake to the client and free every resource.
+    let (mut insurable_loss, mut slashed_flux) = (0u64, 0u64);
+    for (i, group) in host_accounts.chunks(4).enumerate() {
+        let (resource_account, staking_account) = (&group[0], &group[1]);
+        let assignment = &mut job.hosts[i];
+        if assignment.resource != *resource_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let resource_data = resource_account.try_borrow_data()?;
+        let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+        drop(resource_data);
+
+        if assignment.status == state::AssignmentStatus::Revealed {
+            assignment.status = state::AssignmentStatus::Disputed;
+        } else {
+            let (stake_owner, staking_bump, stake) = stake_pool(resource_account, &resource, staking_account)?;
+            let staking_seeds: &[&[u8]] = &[b"stake", stake_owner.as_ref(), &[staking_bump]];
+
+            let slash_amount = (stake as u128 * EXPIRY_SLASH_BPS as u128 / 10_000) as u64;
+            let insurance_cut = (slash_amount as u128 * config.insurance_slash_bps as u128 / 10_000) as u64;
+            if insurance_cut > 0 {
+                transfer_checked(
+                    accounts,
+                    token_program,
+                    staking_account,
+                    flux_mint_account,
+                    insurance_account,
+                    staking_account,
+                    insurance_cut,
+                    &[staking_seeds],
+                )?;
+            }
+            if slash_amount > insurance_cut {
+                transfer_checked(
+                    accounts,
+                    token_program,
+                    staking_account,
+                    flux_mint_account,
+                    client_flux_account,
+                    staking_account,
+                    slash_amount - insurance_cut,
+                    &[staking_seeds],
+                )?;
+            }
+
+            msg!("Slashed host stake for expired job");
+            if resource.fleet == Pubkey::default() {
+                resource.slash(slash_amount);
+                sync_pool_stake(reward_pool_account, &mut resource)?;
+            }
+            resource.reputation_score = resource.reputation_score.saturating_sub(EXPIRY_REPUTATION_PENALTY);
+            assignment.status = state::AssignmentStatus::Slashed;
+            // Work paid for through accepted checkpoints was delivered.
+            insurable_loss += assignment.payment_amount - checkpoint_paid[i];
+            slashed_flux += slash_amount;
+        }
+
+        resource.release(job.requirements.gpu_count, job.requirements.vram_gb());
+        let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+        resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    }
+
+    if let Some((credit_account, balance_before)) = refund_credit {
+        credit_refunds(credit_account, client_token_account, balance_before)?;
+    }
+
+    job.status = state::JobStatus::Failed;
+    job.capacity_released = true;
+    job.insurable_loss = insurable_loss;
+    job.slashed_flux = slashed_flux;
+    let mut job_data_mut = job_account.try_borrow_mut_data()?;
+    job_data_mut.copy_from_slice(&job.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Payment owed to the `index`-th host for checkpoints the client accepted, or zero if the
+/// host never opened a checkpoint log.
+fn checkpoint_payment(
+    job: &state::JobAccount,
+    index: usize,
+    job_account: &AccountInfo,
+    checkpoint_account: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let assignment = &job.hosts[index];
+    let (checkpoint_pda, _bump) = Pubkey::create_program_address(
+        &[b"checkpoints", job_account.key.as_ref(), assignment.host.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if checkpoint_account.key != &checkpoint_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if checkpoint_account.data_is_empty() {
+        return Ok(0);
+    }
+
+    let log_data = checkpoint_account.try_borrow_data()?;
+    let log = state::CheckpointAccount::try_from_slice(&log_data)?;
+    Ok(log.partial_payment(assignment.payment_amount, job.started_at, job.deadline))
+}
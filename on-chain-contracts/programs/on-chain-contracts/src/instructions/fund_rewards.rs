@@ -0,0 +1,40 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use super::deposit_escrow::transfer_checked;
+use super::initialize_rewards::check_rewards_vault;
+
+/// Anyone tops up the reward pool's vault with FLUX to be emitted.
+pub fn fund_rewards(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let funder = &accounts[0];
+    let vault_account = &accounts[1];
+    let token_account = &accounts[2];
+    let token_program = &accounts[3];
+    let mint_account = &accounts[4];
+
+    if !funder.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    // The vault only accepts the FLUX mint it was created with.
+    check_rewards_vault(vault_account)?;
+    transfer_checked(
+        accounts,
+        token_program,
+        token_account,
+        mint_account,
+        vault_account,
+        funder,
+        amount,
+        &[],
+    )?;
+
+    msg!("Funded reward pool");
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Liveness signal from the host or one of its operators, recorded in `last_updated`.
+pub fn heartbeat(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let signer = &accounts[0];
+    let resource_account = &accounts[1];
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    let now = Clock::get()?.unix_timestamp;
+    if !resource.can_operate(signer.key, now) {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    resource.last_updated = now;
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
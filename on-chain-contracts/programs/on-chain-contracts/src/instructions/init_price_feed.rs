@@ -0,0 +1,85 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use spl_token_2022::extension::StateWithExtensions;
+
+use crate::state;
+use super::utils::create_pda_account;
+
+/// Admin opens the USD price feed of an accepted payment mint, or hands an existing feed to a
+/// new publisher. Data: `publisher (32)`.
+pub fn init_price_feed(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let config_account = &accounts[1];
+    let feed_account = &accounts[2];
+    let mint_account = &accounts[3];
+    let system_program = &accounts[4];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let publisher: Pubkey = data[0..32].try_into().unwrap();
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+    if config.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+    if !config.accepts_mint(mint_account.key) {
+        return Err(ProgramError::Custom(53)); // MintNotAllowed
+    }
+
+    let (feed_pda, bump) = Pubkey::create_program_address(
+        &[b"price_feed", mint_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if feed_account.key != &feed_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let feed = if feed_account.data_is_empty() {
+        create_pda_account(
+            accounts,
+            admin,
+            feed_account,
+            system_program,
+            state::PriceFeed::SPACE,
+            &pinocchio::program::ID,
+            &[b"price_feed", mint_account.key.as_ref(), &[bump]],
+        )?;
+
+        let mint_data = mint_account.try_borrow_data()?;
+        let decimals = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?.base.decimals;
+        drop(mint_data);
+
+        state::PriceFeed {
+            mint: *mint_account.key,
+            decimals,
+            publisher,
+            price: 0,
+            conf: 0,
+            expo: 0,
+            publish_time: 0,
+        }
+    } else {
+        let feed_data = feed_account.try_borrow_data()?;
+        let mut feed = state::PriceFeed::try_from_slice(&feed_data)?;
+        drop(feed_data);
+        feed.publisher = publisher;
+        feed
+    };
+
+    let mut feed_data_mut = feed_account.try_borrow_mut_data()?;
+    feed_data_mut.copy_from_slice(&feed.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use borsh::BorshSerialize;
+
+use crate::state;
+use super::utils::create_pda_account;
+
+const BPF_LOADER_UPGRADEABLE_ID: Pubkey = pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Creates the singleton protocol config and the empty auditor pool. Only the program's
+/// upgrade authority may call it, and it becomes the config admin.
+pub fn initialize_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let config_account = &accounts[1];
+    let auditor_pool_account = &accounts[2];
+    let system_program = &accounts[3];
+    let program_data_account = &accounts[4];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // ProgramData is `tag (u32) | slot (u64) | upgrade_authority (Option<Pubkey>) | ...`.
+    let (program_data_pda, _bump) = Pubkey::create_program_address(&[pinocchio::program::ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID)?;
+    if program_data_account.key != &program_data_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let program_data = program_data_account.try_borrow_data()?;
+    if program_data.len() < 45 || program_data[12] != 1 || program_data[13..45] != admin.key[..] {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+    drop(program_data);
+
+    let mut config = parse_config(data)?;
+    config.admin = *admin.key;
+
+    let (config_pda, config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (pool_pda, pool_bump) = Pubkey::create_program_address(&[b"auditors"], &pinocchio::program::ID)?;
+    if auditor_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        admin,
+        config_account,
+        system_program,
+        state::ConfigAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"config", &[config_bump]],
+    )?;
+
+    create_pda_account(
+        accounts,
+        admin,
+        auditor_pool_account,
+        system_program,
+        state::AuditorPool::SPACE,
+        &pinocchio::program::ID,
+        &[b"auditors", &[pool_bump]],
+    )?;
+
+    let mut config_data = config_account.try_borrow_mut_data()?;
+    config_data.copy_from_slice(&config.try_to_vec()?);
+
+    let pool = state::AuditorPool {
+        count: 0,
+        resources: [Pubkey::default(); state::MAX_AUDITORS],
+        hosts: [Pubkey::default(); state::MAX_AUDITORS],
+    };
+    let mut pool_data = auditor_pool_account.try_borrow_mut_data()?;
+    pool_data.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Parses `treasury (32) | protocol_fee_bps (u16) | audit_rate_bps (u16) | min_auditor_stake (u64) |
+/// insurance_fee_bps (u16) | insurance_slash_bps (u16) | insurance_cover_bps (u16) | insurance_max_payout (u64) |
+/// flux_mint (32) | payment_mint_count (u8) | (mint (32), treasury (32))...`.
+pub(crate) fn parse_config(data: &[u8]) -> Result<state::ConfigAccount, ProgramError> {
+    let mut config = state::ConfigAccount {
+        admin: Pubkey::default(),
+        treasury: data[0..32].try_into().unwrap(),
+        protocol_fee_bps: u16::from_le_bytes(data[32..34].try_into().unwrap()),
+        audit_rate_bps: u16::from_le_bytes(data[34..36].try_into().unwrap()),
+        min_auditor_stake: u64::from_le_bytes(data[36..44].try_into().unwrap()),
+        insurance_fee_bps: u16::from_le_bytes(data[44..46].try_into().unwrap()),
+        insurance_slash_bps: u16::from_le_bytes(data[46..48].try_into().unwrap()),
+        insurance_cover_bps: u16::from_le_bytes(data[48..50].try_into().unwrap()),
+        insurance_max_payout: u64::from_le_bytes(data[50..58].try_into().unwrap()),
+        flux_mint: data[58..90].try_into().unwrap(),
+        payment_mint_count: data[90],
+        payment_mints: [state::PaymentMint::EMPTY; state::MAX_PAYMENT_MINTS],
+        attester_count: 0,
+        attesters: [Pubkey::default(); state::MAX_ATTESTERS],
+    };
+
+    let mint_count = config.payment_mint_count as usize;
+    if mint_count > state::MAX_PAYMENT_MINTS || data.len() < 91 + 64 * mint_count {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    for (i, entry) in data[91..].chunks(64).take(mint_count).enumerate() {
+        config.payment_mints[i] = state::PaymentMint {
+            mint: entry[0..32].try_into().unwrap(),
+            treasury: entry[32..64].try_into().unwrap(),
+        };
+    }
+
+    if config.protocol_fee_bps > 10_000
+        || config.audit_rate_bps > 10_000
+        || config.insurance_fee_bps > 10_000
+        || config.insurance_slash_bps > 10_000
+        || config.insurance_cover_bps > 10_000
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(config)
+}
+
+/// Reads the config, checking that `config_account` is the config PDA.
+pub(crate) fn load_config(config_account: &AccountInfo) -> Result<state::ConfigAccount, ProgramError> {
+    let (config_pda, _bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    state::ConfigAccount::try_from_slice(&config_data)
+}
@@ -0,0 +1,56 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use super::deposit_escrow::create_token_vault;
+use super::initialize_config::load_config;
+
+/// Admin creates the insurance pool: a FLUX token account at `[b"insurance"]` owned by the PDA
+/// itself, which settlement fees and expiry slashes are paid into.
+pub fn initialize_insurance(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let config_account = &accounts[1];
+    let insurance_account = &accounts[2];
+    let token_program = &accounts[3];
+    let system_program = &accounts[4];
+    let mint_account = &accounts[5];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let config = load_config(config_account)?;
+    if config.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+    if *mint_account.key != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    let (insurance_pda, insurance_bump) = Pubkey::create_program_address(&[b"insurance"], &pinocchio::program::ID)?;
+    if insurance_account.key != &insurance_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_token_vault(
+        accounts,
+        admin,
+        insurance_account,
+        mint_account,
+        token_program,
+        system_program,
+        &insurance_pda,
+        &[b"insurance", &[insurance_bump]],
+    )
+}
+
+pub(crate) fn check_insurance_pool(insurance_account: &AccountInfo) -> ProgramResult {
+    let (insurance_pda, _insurance_bump) = Pubkey::create_program_address(&[b"insurance"], &pinocchio::program::ID)?;
+    if insurance_account.key != &insurance_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use borsh::BorshSerialize;
+
+use crate::state;
+use super::deposit_escrow::create_token_vault;
+use super::utils::create_pda_account;
+
+/// Admin creates the staking reward pool and the FLUX vault it emits from. Data:
+/// `epoch_length (i64) | emission_per_epoch (u64) | work_share_bps (u16)`. Epoch 0 starts
+/// immediately.
+pub fn initialize_rewards(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let config_account = &accounts[1];
+    let reward_pool_account = &accounts[2];
+    let system_program = &accounts[3];
+    let vault_account = &accounts[4];
+    let token_program = &accounts[5];
+    let mint_account = &accounts[6];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let epoch_length = i64::from_le_bytes(data[0..8].try_into().unwrap());
+    let emission_per_epoch = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let work_share_bps = u16::from_le_bytes(data[16..18].try_into().unwrap());
+    if epoch_length <= 0 || work_share_bps > 10_000 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+    if config.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+    if *mint_account.key != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    let (pool_pda, pool_bump) = Pubkey::create_program_address(&[b"rewards"], &pinocchio::program::ID)?;
+    if reward_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        admin,
+        reward_pool_account,
+        system_program,
+        state::RewardPool::SPACE,
+        &pinocchio::program::ID,
+        &[b"rewards", &[pool_bump]],
+    )?;
+
+    let (vault_pda, vault_bump) = Pubkey::create_program_address(&[b"rewards_vault"], &pinocchio::program::ID)?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    create_token_vault(
+        accounts,
+        admin,
+        vault_account,
+        mint_account,
+        token_program,
+        system_program,
+        &pool_pda,
+        &[b"rewards_vault", &[vault_bump]],
+    )?;
+
+    let pool = state::RewardPool {
+        epoch: 0,
+        epoch_started_at: Clock::get()?.unix_timestamp,
+        epoch_length,
+        emission_per_epoch,
+        work_share_bps,
+        acc_reward_per_stake: 0,
+        total_stake: 0,
+        epoch_gpu_hours: 0,
+        work_rates: [0; state::WORK_RATE_HISTORY],
+    };
+    let mut pool_data = reward_pool_account.try_borrow_mut_data()?;
+    pool_data.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
+
+pub(crate) fn check_rewards_vault(vault_account: &AccountInfo) -> ProgramResult {
+    let (vault_pda, _vault_bump) = Pubkey::create_program_address(&[b"rewards_vault"], &pinocchio::program::ID)?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, slot_hashes::SlotHashes, Sysvar},
+    ProgramResult,
+};
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::state;
+use super::utils::create_pda_account;
+
+/// Permissionless crank issuing a fresh benchmark challenge seed to a resource once
+/// `ChallengeAccount::INTERVAL` has passed since its previous challenge.
+pub fn issue_challenge(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let payer = &accounts[0];
+    let resource_account = &accounts[1];
+    let challenge_account = &accounts[2];
+    let slot_hashes_sysvar = &accounts[3];
+    let system_program = &accounts[4];
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (challenge_pda, bump) = Pubkey::create_program_address(
+        &[b"challenge", resource_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if challenge_account.key != &challenge_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    if resource.status == state::ResourceStatus::Offline {
+        return Err(ProgramError::Custom(4));
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let nonce = if challenge_account.data_is_empty() {
+        create_pda_account(
+            accounts,
+            payer,
+            challenge_account,
+            system_program,
+            state::ChallengeAccount::SPACE,
+            &pinocchio::program::ID,
+            &[b"challenge", resource_account.key.as_ref(), &[bump]],
+        )?;
+
+        0
+    } else {
+        let challenge_data = challenge_account.try_borrow_data()?;
+        let previous = state::ChallengeAccount::try_from_slice(&challenge_data)?;
+        if previous.is_open(now) || now < previous.issued_at + state::ChallengeAccount::INTERVAL {
+            return Err(ProgramError::Custom(31)); // ChallengeNotDue
+        }
+        previous.nonce + 1
+    };
+
+    let slot_hashes = SlotHashes::from_account_info(slot_hashes_sysvar)?;
+    let recent = slot_hashes.get_entry(0).ok_or(ProgramError::InvalidAccountData)?;
+    let seed = hashv(&[&recent.hash, resource_account.key.as_ref(), &nonce.to_le_bytes()]).to_bytes();
+
+    let challenge = state::ChallengeAccount {
+        resource: *resource_account.key,
+        nonce,
+        seed,
+        issued_at: now,
+        deadline: now + state::ChallengeAccount::RESPONSE_WINDOW,
+        output_hash: [0; 32],
+        status: state::ChallengeStatus::Issued,
+    };
+    let mut challenge_data_mut = challenge_account.try_borrow_mut_data()?;
+    challenge_data_mut.copy_from_slice(&challenge.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::stake_fleet::stake_pool;
+
+/// Enrolls a sufficiently staked resource as a spot-check auditor. Fleet members pass their
+/// fleet's stake pool last and are judged on the pooled stake.
+pub fn join_auditor_pool(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+    let config_account = &accounts[2];
+    let auditor_pool_account = &accounts[3];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2));
+    }
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    let (pool_pda, _pool_bump) = Pubkey::create_program_address(&[b"auditors"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda || auditor_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    let stake = if resource.fleet != Pubkey::default() {
+        let staking_account = accounts.get(4).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        stake_pool(resource_account, &resource, staking_account)?.2
+    } else {
+        resource.effective_stake()
+    };
+    if stake < config.min_auditor_stake {
+        return Err(ProgramError::Custom(19)); // InsufficientStake
+    }
+
+    let pool_data = auditor_pool_account.try_borrow_data()?;
+    let mut pool = state::AuditorPool::try_from_slice(&pool_data)?;
+    drop(pool_data);
+    if pool.members().contains(resource_account.key) {
+        return Ok(());
+    }
+    if pool.count as usize == state::MAX_AUDITORS {
+        return Err(ProgramError::Custom(20)); // AuditorPoolFull
+    }
+
+    pool.resources[pool.count as usize] = *resource_account.key;
+    pool.hosts[pool.count as usize] = resource.host;
+    pool.count += 1;
+    let mut pool_data_mut = auditor_pool_account.try_borrow_mut_data()?;
+    pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+
+pub fn leave_auditor_pool(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+    let auditor_pool_account = &accounts[2];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2));
+    }
+
+    let (pool_pda, _bump) = Pubkey::create_program_address(&[b"auditors"], &pinocchio::program::ID)?;
+    if auditor_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let pool_data = auditor_pool_account.try_borrow_data()?;
+    let mut pool = state::AuditorPool::try_from_slice(&pool_data)?;
+    drop(pool_data);
+
+    let index = pool
+        .members()
+        .iter()
+        .position(|r| r == resource_account.key)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let last = pool.count as usize - 1;
+    pool.resources[index] = pool.resources[last];
+    pool.resources[last] = Pubkey::default();
+    pool.hosts[index] = pool.hosts[last];
+    pool.hosts[last] = Pubkey::default();
+    pool.count -= 1;
+
+    let mut pool_data_mut = auditor_pool_account.try_borrow_mut_data()?;
+    pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{transfer_from_escrow, unpack_token_account};
+use super::initialize_config::load_config;
+use super::utils::create_pda_account;
+
+/// Permissionless crank filling the highest-priority crossing bid against the best ask that
+/// can cover it. The fill becomes an `Active` single-GPU job at the resting order's price,
+/// funded from the bid's escrow, with any surplus refunded to the client. If either order of
+/// the pair can no longer be filled, it is removed from the book instead.
+pub fn match_orders(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let cranker = &accounts[0];
+    let book_account = &accounts[1];
+    let job_account = &accounts[2];
+    let resource_account = &accounts[3];
+    let escrow_account = &accounts[4];
+    let vault_account = &accounts[5];
+    let client_token_account = &accounts[6];
+    let token_program = &accounts[7];
+    let system_program = &accounts[8];
+    let config_account = &accounts[9];
+    let mint_account = &accounts[10];
+
+    if !cranker.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let book_data = book_account.try_borrow_data()?;
+    let mut book = state::OrderBook::try_from_slice(&book_data)?;
+    drop(book_data);
+
+    let (bid_index, ask_index) = book.best_match().ok_or(ProgramError::Custom(40))?; // OrdersNotCrossed
+    let bid = book.bids[bid_index];
+    let ask = book.asks[ask_index];
+    if resource_account.key != &ask.resource {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (escrow_pda, _escrow_bump) = Pubkey::create_program_address(
+        &[b"escrow", bid.client.as_ref(), bid.job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if escrow_account.key != &escrow_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (job_pda, job_bump) = Pubkey::create_program_address(
+        &[b"job", bid.client.as_ref(), bid.job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if job_account.key != &job_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The best pair is always matched first, so an order that can never fill would block the
+    // book. Such orders are removed instead, and the crank is called again for the next pair.
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let mut escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    drop(escrow_data);
+    if escrow.status != state::EscrowStatus::Listed || !job_account.data_is_empty() {
+        if escrow.status == state::EscrowStatus::Listed {
+            escrow.status = state::EscrowStatus::Locked;
+            let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
+            escrow_data_mut.copy_from_slice(&escrow.try_to_vec()?);
+        }
+        book.remove_bid(bid_index);
+        msg!("Removed unfillable bid");
+        return save_book(book_account, &book);
+    }
+
+    // The order that rested first sets the price.
+    let price_per_hour = if ask.order_id < bid.order_id { ask.price_per_hour } else { bid.price_per_hour };
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    let mut allowed_gpu_models = [[0; state::MAX_GPU_MODEL_LEN]; state::MAX_ALLOWED_GPU_MODELS];
+    allowed_gpu_models[0] = state::JobRequirements::padded_gpu_model(&book.gpu_class).ok_or(ProgramError::InvalidAccountData)?;
+    let requirements = state::JobRequirements {
+        gpu_count: 1,
+        min_vram_gb: resource.specs.vram_gb,
+        min_cpu_cores: 0,
+        min_compute_rating: 0,
+        allowed_gpu_model_count: 1,
+        allowed_gpu_models,
+        max_price_per_hour: bid.price_per_hour,
+        duration_hours: bid.hours,
+        require_attested: false,
+    };
+    let now = Clock::get()?.unix_timestamp;
+    let deadline = now + bid.hours as i64 * 3600;
+    let config = load_config(config_account)?;
+    if !resource.available_to(&bid.client, now, deadline)
+        || resource.host != ask.host
+        || !requirements.is_satisfied_by(&resource, &config)
+        || !resource.allocate(requirements.gpu_count, requirements.vram_gb())
+    {
+        book.remove_ask(ask_index);
+        msg!("Removed unfillable ask");
+        return save_book(book_account, &book);
+    }
+    let payment = requirements.payment_at(price_per_hour).ok_or(ProgramError::Custom(1))?; // InvalidPrice
+
+    let surplus = escrow.amount.checked_sub(payment).ok_or(ProgramError::Custom(5))?; // InsufficientFunds
+
+    // The job's rent was prefunded by the client in `post_bid`.
+    create_pda_account(
+        accounts,
+        cranker,
+        job_account,
+        system_program,
+        state::JobAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"job", bid.client.as_ref(), bid.job_id.to_le_bytes().as_ref(), &[job_bump]],
+    )?;
+
+    if surplus > 0 {
+        let client_token = unpack_token_account(client_token_account)?;
+        if client_token.owner.to_bytes() != bid.client {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+
+        transfer_from_escrow(
+            accounts,
+            &escrow,
+            escrow_account,
+            vault_account,
+            mint_account,
+            client_token_account,
+            token_program,
+            surplus,
+        )?;
+    }
+
+    msg!("Matched bid with ask");
+    escrow.amount = payment;
+    escrow.status = state::EscrowStatus::Locked;
+    escrow.host = ask.host;
+    let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
+    escrow_data_mut.copy_from_slice(&escrow.try_to_vec()?);
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    let mut hosts = [state::HostAssignment::EMPTY; state::MAX_HOSTS];
+    hosts[0] = state::HostAssignment {
+        host: ask.host,
+        resource: ask.resource,
+        payment_amount: payment,
+        ..state::HostAssignment::EMPTY
+    };
+    let job = state::JobAccount {
+        job_id: bid.job_id,
+        client: bid.client,
+        status: state::JobStatus::Active,
+        requirements,
+        hosts,
+        host_count: 1,
+        quorum: 1,
+        result_hash: [0; 32],
+        deadline,
+        started_at: now,
+        payment_amount: payment,
+        escrow_account: *escrow_account.key,
+        audit_status: state::AuditStatus::Unchecked,
+        auditor: Pubkey::default(),
+        auditor_host: Pubkey::default(),
+        audit_deadline: 0,
+        quorum_slot: 0,
+        insurance_claimed: false,
+        insurable_loss: 0,
+        slashed_flux: 0,
+        mint: escrow.mint,
+        capacity_released: false,
+    };
+    let mut job_data = job_account.try_borrow_mut_data()?;
+    job_data.copy_from_slice(&job.try_to_vec()?);
+
+    book.remove_bid(bid_index);
+    if ask.hours == bid.hours {
+        book.remove_ask(ask_index);
+    } else {
+        book.asks[ask_index].hours -= bid.hours;
+    }
+    save_book(book_account, &book)
+}
+
+fn save_book(book_account: &AccountInfo, book: &state::OrderBook) -> ProgramResult {
+    let mut book_data_mut = book_account.try_borrow_mut_data()?;
+    book_data_mut.copy_from_slice(&book.try_to_vec()?);
+    Ok(())
+}
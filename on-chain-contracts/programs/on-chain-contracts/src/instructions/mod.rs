@@ -2,8 +2,73 @@ pub mod register_resource;
 pub mod update_resource_status;
 pub mod start_job;
 pub mod submit_job_result;
-pub mod resolve_job;
 pub mod deposit_escrow;
 pub mod release_payment;
 pub mod stake_flux;
 pub mod unstake_flux;
+pub mod expire_job;
+pub mod reveal_job_result;
+pub mod settle_job;
+pub mod initialize_config;
+pub mod update_config;
+pub mod join_auditor_pool;
+pub mod leave_auditor_pool;
+pub mod submit_audit;
+pub mod sample_audit;
+pub mod submit_checkpoint;
+pub mod accept_checkpoint;
+pub mod register_attester;
+pub mod revoke_attester;
+pub mod attest_resource;
+pub mod issue_challenge;
+pub mod submit_challenge_response;
+pub mod verify_challenge;
+pub mod expire_challenge;
+pub mod post_job;
+pub mod place_bid;
+pub mod accept_bid;
+pub mod award_job;
+pub mod drop_bid;
+pub mod create_order_book;
+pub mod post_ask;
+pub mod post_bid;
+pub mod cancel_order;
+pub mod match_orders;
+pub mod create_lease;
+pub mod claim_lease;
+pub mod terminate_lease;
+pub mod deposit_credit;
+pub mod withdraw_credit;
+pub mod create_org;
+pub mod update_org;
+pub mod set_org_member;
+pub mod add_operator;
+pub mod revoke_operator;
+pub mod heartbeat;
+pub mod create_fleet;
+pub mod add_fleet_resource;
+pub mod remove_fleet_resource;
+pub mod stake_fleet;
+pub mod unstake_fleet;
+pub mod update_fleet;
+pub mod withdraw_fleet_unbonded;
+pub mod set_delegator_share;
+pub mod delegate_stake;
+pub mod undelegate_stake;
+pub mod withdraw_unbonded;
+pub mod initialize_rewards;
+pub mod fund_rewards;
+pub mod advance_epoch;
+pub mod sync_rewards;
+pub mod claim_rewards;
+pub mod compound_rewards;
+pub mod claim_insurance;
+pub mod set_mint_price;
+pub mod initialize_insurance;
+pub mod utils;
+pub mod init_price_feed;
+pub mod update_price_feed;
+pub mod set_usd_price;
+pub mod close_job;
+pub mod close_bid;
+pub mod close_lease;
@@ -0,0 +1,98 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::initialize_config::load_config;
+use super::utils::create_pda_account;
+
+/// A qualifying host bids a per-GPU hourly price on a posted job.
+pub fn place_bid(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let job_account = &accounts[1];
+    let auction_account = &accounts[2];
+    let resource_account = &accounts[3];
+    let bid_account = &accounts[4];
+    let system_program = &accounts[5];
+    let config_account = &accounts[6];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let price_per_hour = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let job_data = job_account.try_borrow_data()?;
+    let job = state::JobAccount::try_from_slice(&job_data)?;
+    if job.status != state::JobStatus::Pending {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+
+    let auction_data = auction_account.try_borrow_data()?;
+    let mut auction = state::AuctionAccount::try_from_slice(&auction_data)?;
+    drop(auction_data);
+    if auction.job != *job_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let now = Clock::get()?.unix_timestamp;
+    if now >= auction.bidding_ends_at {
+        return Err(ProgramError::Custom(35)); // BiddingClosed
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2));
+    }
+    if price_per_hour == 0 {
+        return Err(ProgramError::Custom(1)); // InvalidPrice
+    }
+    let config = load_config(config_account)?;
+    if !job.requirements.is_satisfied_by(&resource, &config) || !job.requirements.accepts_price(price_per_hour) {
+        return Err(ProgramError::Custom(34)); // RequirementsNotMet
+    }
+
+    let (bid_pda, bump) = Pubkey::create_program_address(
+        &[b"bid", job_account.key.as_ref(), resource_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if bid_account.key != &bid_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        host,
+        bid_account,
+        system_program,
+        state::BidAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"bid", job_account.key.as_ref(), resource_account.key.as_ref(), &[bump]],
+    )?;
+
+    let score = state::AuctionAccount::score(price_per_hour, resource.reputation_score);
+    let bid = state::BidAccount {
+        job: *job_account.key,
+        resource: *resource_account.key,
+        host: *host.key,
+        price_per_hour,
+        placed_at: now,
+        score,
+    };
+    let mut bid_data = bid_account.try_borrow_mut_data()?;
+    bid_data.copy_from_slice(&bid.try_to_vec()?);
+
+    if score < auction.best_score {
+        auction.best_score = score;
+        auction.best_bid = *bid_account.key;
+    }
+    auction.bid_count += 1;
+    let mut auction_data_mut = auction_account.try_borrow_mut_data()?;
+    auction_data_mut.copy_from_slice(&auction.try_to_vec()?);
+
+    Ok(())
+}
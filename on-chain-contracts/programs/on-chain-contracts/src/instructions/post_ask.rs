@@ -0,0 +1,62 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Host offers GPU-hours of one of its resources on the book for the resource's GPU class.
+pub fn post_ask(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let book_account = &accounts[1];
+    let resource_account = &accounts[2];
+
+    let price_per_hour = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let hours = u32::from_le_bytes(data[8..12].try_into().unwrap());
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if price_per_hour == 0 {
+        return Err(ProgramError::Custom(1)); // InvalidPrice
+    }
+    if hours == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    let book_data = book_account.try_borrow_data()?;
+    let mut book = state::OrderBook::try_from_slice(&book_data)?;
+    drop(book_data);
+
+    let gpu_model = if resource.attested { &resource.attestation.gpu_model } else { &resource.specs.gpu_model };
+    if *gpu_model != book.gpu_class {
+        return Err(ProgramError::Custom(34)); // RequirementsNotMet
+    }
+
+    let ask = state::AskOrder {
+        order_id: book.next_order_id,
+        host: *host.key,
+        resource: *resource_account.key,
+        price_per_hour,
+        hours,
+    };
+    if !book.insert_ask(ask) {
+        return Err(ProgramError::Custom(41)); // OrderBookFull
+    }
+    book.next_order_id += 1;
+
+    msg!("Posted ask");
+    let mut book_data_mut = book_account.try_borrow_mut_data()?;
+    book_data_mut.copy_from_slice(&book.try_to_vec()?);
+
+    Ok(())
+}
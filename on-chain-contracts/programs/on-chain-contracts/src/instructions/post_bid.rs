@@ -0,0 +1,104 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::utils::transfer_lamports;
+
+/// Client bids for GPU-hours on a book. The escrow for `job_id` must already be locked
+/// with the full bid value in FLUX, the book's quote mint; the fill runs under that job ID.
+/// The escrow is reserved for the bid until it is filled or cancelled, and the client
+/// prefunds the rent of the job account the fill will create.
+pub fn post_bid(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let book_account = &accounts[1];
+    let escrow_account = &accounts[2];
+    let config_account = &accounts[3];
+    let job_account = &accounts[4];
+    let system_program = &accounts[5];
+
+    let job_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let price_per_hour = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let hours = u32::from_le_bytes(data[16..20].try_into().unwrap());
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if price_per_hour == 0 {
+        return Err(ProgramError::Custom(1)); // InvalidPrice
+    }
+    if hours == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let value = price_per_hour.checked_mul(hours as u64).ok_or(ProgramError::Custom(1))?; // InvalidPrice
+
+    let (escrow_pda, _escrow_bump) = Pubkey::create_program_address(
+        &[b"escrow", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let mut escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    drop(escrow_data);
+    if escrow_account.key != &escrow_pda || escrow.status != state::EscrowStatus::Locked {
+        return Err(ProgramError::Custom(6)); // EscrowNotLocked
+    }
+    if escrow.amount < value {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+    if escrow.mint != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    let (job_pda, _job_bump) = Pubkey::create_program_address(
+        &[b"job", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if job_account.key != &job_pda || !job_account.data_is_empty() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let book_data = book_account.try_borrow_data()?;
+    let mut book = state::OrderBook::try_from_slice(&book_data)?;
+    drop(book_data);
+    let bid = state::BidOrder {
+        order_id: book.next_order_id,
+        client: *client.key,
+        job_id,
+        price_per_hour,
+        hours,
+    };
+    if !book.insert_bid(bid) {
+        return Err(ProgramError::Custom(41)); // OrderBookFull
+    }
+    book.next_order_id += 1;
+
+    let job_rent = Rent::get()?.minimum_balance(state::JobAccount::SPACE);
+    let prefund = job_rent.saturating_sub(job_account.lamports());
+    if prefund > 0 {
+        transfer_lamports(accounts, client, job_account, system_program, prefund, &[])?;
+    }
+
+    msg!("Posted bid");
+    escrow.status = state::EscrowStatus::Listed;
+    let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
+    escrow_data_mut.copy_from_slice(&escrow.try_to_vec()?);
+
+    let mut book_data_mut = book_account.try_borrow_mut_data()?;
+    book_data_mut.copy_from_slice(&book.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::utils::create_pda_account;
+
+/// Posts an open `Pending` job for hosts to bid on. The client's escrow must already
+/// hold the job's maximum budget; the surplus is refunded when a bid is awarded.
+pub fn post_job(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let client = &accounts[0];
+    let job_account = &accounts[1];
+    let auction_account = &accounts[2];
+    let escrow_account = &accounts[3];
+    let system_program = &accounts[4];
+
+    let job_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let deadline = i64::from_le_bytes(data[8..16].try_into().unwrap());
+    let bidding_ends_at = i64::from_le_bytes(data[16..24].try_into().unwrap());
+    let requirements = state::JobRequirements::try_from_slice(&data[24..])?;
+
+    if !client.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if bidding_ends_at <= now || deadline <= bidding_ends_at {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    if requirements.gpu_count == 0
+        || requirements.duration_hours == 0
+        || requirements.allowed_gpu_model_count as usize > state::MAX_ALLOWED_GPU_MODELS
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let max_budget = requirements
+        .payment_at(requirements.max_price_per_hour)
+        .ok_or(ProgramError::Custom(1))?; // InvalidPrice
+
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    let (escrow_pda, _escrow_bump) = Pubkey::create_program_address(
+        &[b"escrow", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if escrow_account.key != &escrow_pda || escrow.status != state::EscrowStatus::Locked {
+        return Err(ProgramError::Custom(6)); // EscrowNotLocked
+    }
+    if escrow.amount < max_budget {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    let (job_pda, job_bump) = Pubkey::create_program_address(
+        &[b"job", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if job_account.key != &job_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (auction_pda, auction_bump) = Pubkey::create_program_address(
+        &[b"auction", job_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if auction_account.key != &auction_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        client,
+        job_account,
+        system_program,
+        state::JobAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"job", client.key.as_ref(), job_id.to_le_bytes().as_ref(), &[job_bump]],
+    )?;
+
+    create_pda_account(
+        accounts,
+        client,
+        auction_account,
+        system_program,
+        state::AuctionAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"auction", job_account.key.as_ref(), &[auction_bump]],
+    )?;
+
+    let job = state::JobAccount {
+        job_id,
+        client: *client.key,
+        status: state::JobStatus::Pending,
+        requirements,
+        hosts: [state::HostAssignment::EMPTY; state::MAX_HOSTS],
+        host_count: 0,
+        quorum: 1,
+        result_hash: [0; 32],
+        deadline,
+        started_at: 0,
+        payment_amount: 0,
+        escrow_account: *escrow_account.key,
+        audit_status: state::AuditStatus::Unchecked,
+        auditor: Pubkey::default(),
+        auditor_host: Pubkey::default(),
+        audit_deadline: 0,
+        quorum_slot: 0,
+        insurance_claimed: false,
+        insurable_loss: 0,
+        slashed_flux: 0,
+        mint: escrow.mint,
+        capacity_released: false,
+    };
+    let mut job_data = job_account.try_borrow_mut_data()?;
+    job_data.copy_from_slice(&job.try_to_vec()?);
+
+    let auction = state::AuctionAccount {
+        job: *job_account.key,
+        bidding_ends_at,
+        bid_count: 0,
+        best_bid: Pubkey::default(),
+        best_score: u64::MAX,
+    };
+    let mut auction_data = auction_account.try_borrow_mut_data()?;
+    auction_data.copy_from_slice(&auction.try_to_vec()?);
+
+    Ok(())
+}
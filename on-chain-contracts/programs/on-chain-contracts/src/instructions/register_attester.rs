@@ -0,0 +1,72 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use borsh::BorshSerialize;
+
+use crate::state;
+use super::utils::create_pda_account;
+
+/// Admin registers a benchmarking service key allowed to attest host hardware and adds it to
+/// the config's active set. Fails with `AttestersFull` once `MAX_ATTESTERS` are active.
+pub fn register_attester(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let config_account = &accounts[1];
+    let attester_account = &accounts[2];
+    let system_program = &accounts[3];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let attester_key: Pubkey = data[0..32].try_into().unwrap();
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let mut config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+    if config.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+    let count = config.attester_count as usize;
+    if count == state::MAX_ATTESTERS {
+        return Err(ProgramError::Custom(30)); // AttestersFull
+    }
+
+    let (attester_pda, bump) = Pubkey::create_program_address(
+        &[b"attester", attester_key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if attester_account.key != &attester_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    create_pda_account(
+        accounts,
+        admin,
+        attester_account,
+        system_program,
+        state::AttesterAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"attester", attester_key.as_ref(), &[bump]],
+    )?;
+
+    let attester = state::AttesterAccount {
+        key: attester_key,
+        active: true,
+    };
+    let mut attester_data = attester_account.try_borrow_mut_data()?;
+    attester_data.copy_from_slice(&attester.try_to_vec()?);
+
+    config.attesters[count] = attester_key;
+    config.attester_count += 1;
+    let mut config_data_mut = config_account.try_borrow_mut_data()?;
+    config_data_mut.copy_from_slice(&config.try_to_vec()?);
+
+    Ok(())
+}
@@ -1,16 +1,14 @@
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{AccountMeta, Instruction},
     msg,
-    program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
-    system_instruction,
     ProgramResult,
 };
 use borsh::BorshSerialize;
 
 use crate::state;
+use super::utils::create_pda_account;
 
 pub fn register_resource(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let host = &accounts[0];
@@ -23,6 +21,10 @@ pub fn register_resource(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         return Err(ProgramError::Custom(1)); // InvalidPrice
     }
 
+    if specs.gpu_count == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
     if !host.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
@@ -36,28 +38,14 @@ pub fn register_resource(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let create_account_ix = system_instruction::create_account(
-        host.key,
-        resource_account.key,
-        1000000, 
-        state::ResourceAccount::SPACE as u64,
-        &pinocchio::program::ID,
-    );
-
-    let create_account_instruction = Instruction {
-        program_id: system_program.key,
-        accounts: vec![
-            AccountMeta::new(*host.key, true),
-            AccountMeta::new(*resource_account.key, false),
-            AccountMeta::new(*system_program.key, false),
-        ],
-        data: create_account_ix.data,
-    };
-
-    invoke_signed(
-        &create_account_instruction,
+    create_pda_account(
         accounts,
-        &[&[b"resource", host.key.as_ref(), specs.id.to_le_bytes().as_ref(), &[bump]]],
+        host,
+        resource_account,
+        system_program,
+        state::ResourceAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"resource", host.key.as_ref(), specs.id.to_le_bytes().as_ref(), &[bump]],
     )?;
 
     let mut account_data = resource_account.try_borrow_mut_data()?;
@@ -68,6 +56,35 @@ pub fn register_resource(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         reputation_score: 1000,
         staked_flux: 0,
         last_updated: 0, 
+        attested: false,
+        attestation: state::AttestedSpecs::default(),
+        allocated_gpus: 0,
+        allocated_vram_gb: 0,
+        lessee: Pubkey::default(),
+        lease_start: 0,
+        lease_end: 0,
+        operator_count: 0,
+        operators: [state::OperatorKey::EMPTY; state::MAX_OPERATORS],
+        fleet: Pubkey::default(),
+        delegated_flux: 0,
+        delegation_shares: 0,
+        delegation_generation: 0,
+        unbonding_flux: 0,
+        unbonding_shares: 0,
+        unbonding_generation: 0,
+        delegator_share_bps: 0,
+        pending_delegator_share_bps: 0,
+        delegator_share_changes_at: 0,
+        reward_stake: 0,
+        reward_delegated_stake: 0,
+        reward_debt: 0,
+        pending_host_rewards: 0,
+        pending_delegator_rewards: 0,
+        work_epoch: 0,
+        work_gpu_hours: 0,
+        mint_price_count: 0,
+        mint_prices: [state::MintPrice::EMPTY; state::MAX_PAYMENT_MINTS],
+        usd_price_per_hour: 0,
     };
     account_data.copy_from_slice(&resource.try_to_vec()?);
 
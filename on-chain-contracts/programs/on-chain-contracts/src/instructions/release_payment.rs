@@ -2,34 +2,91 @@ use pinocchio::{
     account_info::AccountInfo,
     msg,
     program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 
 use crate::state;
+use super::deposit_escrow::{transfer_from_escrow, unpack_token_account};
 
+/// The client releases the whole escrow to one of the job's hosts, paid out of the escrow's
+/// token vault, and completes the job. Every assigned resource is passed after the job and
+/// the escrow's mint, in assignment order, so that the capacity the job held is returned.
 pub fn release_payment(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let client = &accounts[0];
     let host = &accounts[1];
     let escrow_account = &accounts[2];
-    let token_account = &accounts[3];
-    let token_program = &accounts[4];
+    let vault_account = &accounts[3];
+    let token_account = &accounts[4];
+    let token_program = &accounts[5];
+    let job_account = &accounts[6];
+    let mint_account = &accounts[7];
+    let resource_accounts = &accounts[8..];
 
     if !client.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
+    let job_data = job_account.try_borrow_data()?;
+    let mut job = state::JobAccount::try_from_slice(&job_data)?;
+    if job.escrow_account != *escrow_account.key || job.client != *client.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !matches!(job.status, state::JobStatus::Active | state::JobStatus::Completed) {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    if resource_accounts.len() != job.host_count as usize {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if !job.assignments().iter().any(|a| a.host == *host.key) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // Escrow stays locked while a sampled audit is outstanding or has failed.
+    match job.audit_status {
+        state::AuditStatus::Pending if Clock::get()?.unix_timestamp <= job.audit_deadline => {
+            return Err(ProgramError::Custom(22)); // AuditPending
+        }
+        state::AuditStatus::Failed => return Err(ProgramError::Custom(23)), // AuditFailed
+        _ => {}
+    }
+
     let escrow_data = escrow_account.try_borrow_data()?;
     let escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
     if escrow.status != state::EscrowStatus::Locked {
         return Err(ProgramError::Custom(6));
     }
+    let host_token = unpack_token_account(token_account)?;
+    if host_token.owner.to_bytes() != *host.key {
+        return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+    }
+    if escrow.mint != job.mint || host_token.mint.to_bytes() != job.mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+    drop(escrow_data);
+    drop(job_data);
 
-    msg!("Releasing {} FLUX to host {}", escrow.amount, host.key);
+    transfer_from_escrow(accounts, &escrow, escrow_account, vault_account, mint_account, token_account, token_program, escrow.amount)?;
+    msg!("Released escrow to host");
 
     let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
     let mut escrow_mut = state::EscrowAccount::try_from_slice(&escrow_data_mut)?;
     escrow_mut.status = state::EscrowStatus::Released;
     escrow_data_mut.copy_from_slice(&escrow_mut.try_to_vec()?);
 
+    for (assignment, resource_account) in job.assignments().iter().zip(resource_accounts) {
+        if assignment.resource != *resource_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+        let mut resource = state::ResourceAccount::try_from_slice(&resource_data_mut)?;
+        resource.release(job.requirements.gpu_count, job.requirements.vram_gb());
+        resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    }
+
+    job.status = state::JobStatus::Completed;
+    job.capacity_released = true;
+    let mut job_data_mut = job_account.try_borrow_mut_data()?;
+    job_data_mut.copy_from_slice(&job.try_to_vec()?);
+
     Ok(())
 }
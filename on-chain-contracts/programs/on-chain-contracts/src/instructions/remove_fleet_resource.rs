@@ -0,0 +1,52 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Operator takes an idle resource out of its fleet, leaving it without stake.
+pub fn remove_fleet_resource(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let operator = &accounts[0];
+    let fleet_account = &accounts[1];
+    let resource_account = &accounts[2];
+
+    if !operator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let fleet_data = fleet_account.try_borrow_data()?;
+    let mut fleet = state::FleetAccount::try_from_slice(&fleet_data)?;
+    drop(fleet_data);
+    if fleet.operator != *operator.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    // Running jobs stay backed by the pool until they settle or expire.
+    if resource.allocated_gpus > 0 {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+
+    let index = fleet
+        .resources()
+        .iter()
+        .position(|r| r == resource_account.key)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let count = fleet.resource_count as usize;
+    fleet.resources[index..count].rotate_left(1);
+    fleet.resources[count - 1] = Pubkey::default();
+    fleet.resource_count -= 1;
+    let mut fleet_data_mut = fleet_account.try_borrow_mut_data()?;
+    fleet_data_mut.copy_from_slice(&fleet.try_to_vec()?);
+
+    resource.fleet = Pubkey::default();
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
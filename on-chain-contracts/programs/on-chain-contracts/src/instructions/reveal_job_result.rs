@@ -0,0 +1,60 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::add_operator::operated_assignment;
+
+/// Reveal phase of result submission: the host discloses `result_hash` and `salt`,
+/// which must match the commitment posted via `submit_job_result`. The reveal that first
+/// completes a quorum records its slot for `sample_audit`. An operator reveals on the host's
+/// behalf by passing the resource account after the job.
+pub fn reveal_job_result(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let job_account = &accounts[1];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let result_hash: [u8; 32] = data[0..32].try_into().unwrap();
+    let salt: [u8; 32] = data[32..64].try_into().unwrap();
+
+    let account_data = job_account.try_borrow_data()?;
+    let job = state::JobAccount::try_from_slice(&account_data)?;
+    let index = operated_assignment(&job, host, accounts.get(2))?;
+    if job.status != state::JobStatus::Active || job.hosts[index].status != state::AssignmentStatus::Committed {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+
+    // Reveals open once every assigned host has committed, or once the commit deadline passes.
+    let now = Clock::get()?.unix_timestamp;
+    if job.commit_count() < job.host_count as usize && now <= job.deadline {
+        return Err(ProgramError::Custom(14)); // RevealNotOpen
+    }
+    if now > job.deadline + state::JobAccount::REVEAL_WINDOW {
+        return Err(ProgramError::Custom(11)); // RevealWindowClosed
+    }
+
+    if state::JobAccount::result_commitment(&result_hash, &salt, &job.hosts[index].host) != job.hosts[index].result_commitment {
+        return Err(ProgramError::Custom(12)); // CommitmentMismatch
+    }
+
+    let mut account_data_mut = job_account.try_borrow_mut_data()?;
+    let mut job_mut = state::JobAccount::try_from_slice(&account_data_mut)?;
+    job_mut.hosts[index].result_hash = result_hash;
+    job_mut.hosts[index].status = state::AssignmentStatus::Revealed;
+
+    // Sampling happens later in `sample_audit`, from the hash of a slot after this one, so the
+    // host cannot time its reveal to dodge the audit.
+    if job_mut.quorum_slot == 0 && job_mut.quorum_hash().is_some() {
+        job_mut.quorum_slot = Clock::get()?.slot;
+    }
+
+    account_data_mut.copy_from_slice(&job_mut.try_to_vec()?);
+
+    Ok(())
+}
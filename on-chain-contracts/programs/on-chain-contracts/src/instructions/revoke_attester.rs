@@ -0,0 +1,54 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Admin deactivates an attester and removes it from the config's active set, so that resources
+/// it attested stop matching on their attested specs.
+pub fn revoke_attester(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let config_account = &accounts[1];
+    let attester_account = &accounts[2];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (config_pda, _bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let mut config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+    if config.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+
+    let mut attester_data_mut = attester_account.try_borrow_mut_data()?;
+    let mut attester = state::AttesterAccount::try_from_slice(&attester_data_mut)?;
+    let (attester_pda, _attester_bump) = Pubkey::create_program_address(
+        &[b"attester", attester.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if attester_account.key != &attester_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    attester.active = false;
+    attester_data_mut.copy_from_slice(&attester.try_to_vec()?);
+
+    let count = config.attester_count as usize;
+    if let Some(index) = config.attesters[..count].iter().position(|a| *a == attester.key) {
+        config.attesters[index] = config.attesters[count - 1];
+        config.attesters[count - 1] = Pubkey::default();
+        config.attester_count -= 1;
+        let mut config_data_mut = config_account.try_borrow_mut_data()?;
+        config_data_mut.copy_from_slice(&config.try_to_vec()?);
+    }
+
+    Ok(())
+}
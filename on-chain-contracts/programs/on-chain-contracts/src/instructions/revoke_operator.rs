@@ -0,0 +1,42 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Host removes an operator key from the resource with immediate effect.
+pub fn revoke_operator(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let operator: Pubkey = data[0..32].try_into().unwrap();
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    let index = resource
+        .operators()
+        .iter()
+        .position(|o| o.key == operator)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let count = resource.operator_count as usize;
+    resource.operators[index..count].rotate_left(1);
+    resource.operators[count - 1] = state::OperatorKey::EMPTY;
+    resource.operator_count -= 1;
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, slot_hashes::SlotHashes, Sysvar},
+    ProgramResult,
+};
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::state;
+
+/// Permissionless crank that samples a job for a spot-check audit once its quorum is reached.
+/// The seed is the hash of the first slot after the quorum reveal, which the revealing host
+/// could not know. If that slot has already dropped out of `SlotHashes` the job is always
+/// audited, so delaying the crank never helps the hosts.
+pub fn sample_audit(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let job_account = &accounts[0];
+    let config_account = &accounts[1];
+    let auditor_pool_account = &accounts[2];
+    let slot_hashes_sysvar = &accounts[3];
+
+    let account_data = job_account.try_borrow_data()?;
+    let mut job = state::JobAccount::try_from_slice(&account_data)?;
+    drop(account_data);
+    if job.audit_status != state::AuditStatus::Unchecked || job.quorum_slot == 0 {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    let (pool_pda, _pool_bump) = Pubkey::create_program_address(&[b"auditors"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda || auditor_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Entries run from the newest slot to the oldest.
+    let slot_hashes = SlotHashes::from_account_info(slot_hashes_sysvar)?;
+    let mut sample = None;
+    let mut covers_quorum = false;
+    for i in 0..slot_hashes.len() {
+        let entry = slot_hashes.get_entry(i).ok_or(ProgramError::InvalidAccountData)?;
+        if entry.slot() <= job.quorum_slot {
+            covers_quorum = true;
+            break;
+        }
+        sample = Some(entry.hash);
+    }
+    let sample = sample.ok_or(ProgramError::Custom(24))?; // AuditNotReady
+
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    let pool_data = auditor_pool_account.try_borrow_data()?;
+    let pool = state::AuditorPool::try_from_slice(&pool_data)?;
+
+    let seed = hashv(&[&sample, job_account.key.as_ref()]).to_bytes();
+    let roll = u64::from_le_bytes(seed[0..8].try_into().unwrap()) % 10_000;
+
+    let job_hosts: Vec<Pubkey> = job.assignments().iter().map(|a| a.host).collect();
+    let sampled = roll < config.audit_rate_bps as u64 || (!covers_quorum && config.audit_rate_bps > 0);
+    let auditor = if sampled {
+        pool.select(&seed, &job_hosts)
+    } else {
+        None
+    };
+
+    match auditor {
+        Some(auditor) => {
+            job.audit_status = state::AuditStatus::Pending;
+            job.auditor = auditor;
+            job.audit_deadline = Clock::get()?.unix_timestamp + state::JobAccount::AUDIT_WINDOW;
+        }
+        None => job.audit_status = state::AuditStatus::Skipped,
+    }
+
+    let mut account_data = job_account.try_borrow_mut_data()?;
+    account_data.copy_from_slice(&job.try_to_vec()?);
+
+    Ok(())
+}
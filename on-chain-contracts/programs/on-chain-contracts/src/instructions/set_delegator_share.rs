@@ -0,0 +1,47 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Host sets the share of its job earnings, in basis points, paid to the resource's delegators.
+/// Raises apply at once; cuts take effect after the delegation unbonding period, so delegators
+/// can undelegate and withdraw before earning less.
+pub fn set_delegator_share(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let delegator_share_bps = u16::from_le_bytes(data[0..2].try_into().unwrap());
+    if delegator_share_bps > 10_000 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    resource.delegator_share_bps = resource.delegator_share_bps_at(now);
+    if delegator_share_bps >= resource.delegator_share_bps {
+        resource.delegator_share_bps = delegator_share_bps;
+        resource.pending_delegator_share_bps = 0;
+        resource.delegator_share_changes_at = 0;
+    } else {
+        resource.pending_delegator_share_bps = delegator_share_bps;
+        resource.delegator_share_changes_at = now + state::DelegationAccount::UNBONDING_PERIOD;
+    }
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
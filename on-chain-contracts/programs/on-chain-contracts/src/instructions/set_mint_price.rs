@@ -0,0 +1,66 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Host quotes a resource's price per GPU-hour in an accepted payment mint other than FLUX.
+/// Data: `mint (32) | price_per_hour (u64)`; a zero price withdraws the quote.
+pub fn set_mint_price(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+    let config_account = &accounts[2];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mint: Pubkey = data[0..32].try_into().unwrap();
+    let price_per_hour = u64::from_le_bytes(data[32..40].try_into().unwrap());
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+    // FLUX is priced by `specs.price_per_hour`.
+    if mint == config.flux_mint || !config.accepts_mint(&mint) {
+        return Err(ProgramError::Custom(53)); // MintNotAllowed
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    let count = resource.mint_price_count as usize;
+    let existing = resource.mint_prices().iter().position(|p| p.mint == mint);
+    match (existing, price_per_hour) {
+        (Some(index), 0) => {
+            resource.mint_prices[index..count].rotate_left(1);
+            resource.mint_prices[count - 1] = state::MintPrice::EMPTY;
+            resource.mint_price_count -= 1;
+        }
+        (Some(index), _) => resource.mint_prices[index].price_per_hour = price_per_hour,
+        (None, 0) => {}
+        (None, _) => {
+            if count == state::MAX_PAYMENT_MINTS {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            resource.mint_prices[count] = state::MintPrice { mint, price_per_hour };
+            resource.mint_price_count += 1;
+        }
+    }
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::utils::create_pda_account;
+
+/// Org admin grants a key spend permission with a per-period limit, updates it, or revokes it.
+pub fn set_org_member(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let org_account = &accounts[1];
+    let member_account = &accounts[2];
+    let system_program = &accounts[3];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let member_key: Pubkey = data[0..32].try_into().unwrap();
+    let period_limit = u64::from_le_bytes(data[32..40].try_into().unwrap());
+    let active = data[40] != 0;
+
+    let org_data = org_account.try_borrow_data()?;
+    let org = state::OrgAccount::try_from_slice(&org_data)?;
+    drop(org_data);
+    if org.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+
+    let (member_pda, bump) = Pubkey::create_program_address(
+        &[b"member", org_account.key.as_ref(), member_key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if member_account.key != &member_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut member = if member_account.data_is_empty() {
+        create_pda_account(
+            accounts,
+            admin,
+            member_account,
+            system_program,
+            state::OrgMember::SPACE,
+            &pinocchio::program::ID,
+            &[b"member", org_account.key.as_ref(), member_key.as_ref(), &[bump]],
+        )?;
+
+        state::OrgMember {
+            org: *org_account.key,
+            member: member_key,
+            budget: state::SpendBudget {
+                period_start: Clock::get()?.unix_timestamp,
+                ..Default::default()
+            },
+            active,
+        }
+    } else {
+        let member_data = member_account.try_borrow_data()?;
+        state::OrgMember::try_from_slice(&member_data)?
+    };
+
+    member.budget.limit = period_limit;
+    member.active = active;
+    let mut member_data_mut = member_account.try_borrow_mut_data()?;
+    member_data_mut.copy_from_slice(&member.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Host quotes a resource's price in micro-USD per GPU-hour, converted to the job's mint at
+/// the oracle price when a job starts. Zero goes back to token prices.
+pub fn set_usd_price(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let usd_price_per_hour = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+    if resource.host != *host.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    resource.usd_price_per_hour = usd_price_per_hour;
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{transfer_from_escrow, unpack_token_account};
+use super::initialize_insurance::check_insurance_pool;
+use super::sync_rewards::load_reward_pool;
+use super::withdraw_credit::{check_refund_credit, credit_refunds};
+
+/// Permissionless settlement of a job whose revealed results reached quorum: pays every
+/// agreeing host its share less the protocol fee, marks dissenters for dispute and refunds
+/// the rest to the client. The protocol fee goes to the auditor if the job was audited, and
+/// to the mint's treasury otherwise. On FLUX jobs, `insurance_fee_bps` of the fee is paid into
+/// the insurance pool and each resource's delegators receive `delegator_share_bps` of the host's
+/// earnings, compounded into their delegated stake. GPU-hours served by agreeing hosts are credited to the current
+/// reward epoch once the reward pool exists.
+///
+/// Accounts after the job's mint come in `(resource, host_token_account,
+/// staking_account)` triples, one per assigned host in assignment order. Jobs funded by an
+/// organization pass its credit account last.
+pub fn settle_job(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let _cranker = &accounts[0];
+    let job_account = &accounts[1];
+    let escrow_account = &accounts[2];
+    let vault_account = &accounts[3];
+    let client_token_account = &accounts[4];
+    let token_program = &accounts[5];
+    let config_account = &accounts[6];
+    let treasury_token_account = &accounts[7];
+    let auditor_token_account = &accounts[8];
+    let reward_pool_account = &accounts[9];
+    let insurance_account = &accounts[10];
+    let mint_account = &accounts[11];
+
+    let account_data = job_account.try_borrow_data()?;
+    let mut job = state::JobAccount::try_from_slice(&account_data)?;
+    drop(account_data);
+
+    if job.status != state::JobStatus::Active {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    if job.escrow_account != *escrow_account.key || accounts.len() < 12 + 3 * job.host_count as usize {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let host_accounts = &accounts[12..12 + 3 * job.host_count as usize];
+
+    let all_revealed = job
+        .assignments()
+        .iter()
+        .all(|a| a.status == state::AssignmentStatus::Revealed);
+    let now = Clock::get()?.unix_timestamp;
+    if !all_revealed && now <= job.deadline + state::JobAccount::REVEAL_WINDOW {
+        return Err(ProgramError::Custom(16)); // SettlementNotReady
+    }
+    let agreed_hash = job.quorum_hash().ok_or(ProgramError::Custom(15))?; // QuorumNotReached
+
+    // An auditor that misses its window simply forfeits the fee.
+    match job.audit_status {
+        state::AuditStatus::Pending if now <= job.audit_deadline => {
+            return Err(ProgramError::Custom(22)); // AuditPending
+        }
+        state::AuditStatus::Unchecked => return Err(ProgramError::Custom(24)), // AuditNotReady
+        state::AuditStatus::Failed => return Err(ProgramError::Custom(23)), // AuditFailed
+        _ => {}
+    }
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+
+    let fee_account = if job.audit_status == state::AuditStatus::Passed {
+        let auditor_token = unpack_token_account(auditor_token_account)?;
+        if auditor_token.owner.to_bytes() != job.auditor_host || auditor_token.mint.to_bytes() != job.mint {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+        auditor_token_account
+    } else {
+        if Some(*treasury_token_account.key) != config.treasury_for(&job.mint) {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+        treasury_token_account
+    };
+
+    check_insurance_pool(insurance_account)?;
+    // Work is only credited once the reward pool has been initialized.
+    let mut pool = load_reward_pool(reward_pool_account)?;
+    let gpu_hours = job.requirements.gpu_count as u64 * job.requirements.duration_hours as u64;
+    // Stake vaults and the insurance pool only hold FLUX.
+    let pays_flux = job.mint == config.flux_mint;
+
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let mut escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+    drop(escrow_data);
+    if escrow.status != state::EscrowStatus::Locked {
+        return Err(ProgramError::Custom(6)); // EscrowNotLocked
+    }
+
+    // An organization's job refunds into its credit vault, passed as the client token account.
+    let refund_credit = if escrow.refund_owner == Pubkey::default() {
+        let client_token = unpack_token_account(client_token_account)?;
+        if client_token.owner.to_bytes() != job.client || client_token.mint.to_bytes() != job.mint {
+            return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+        }
+        None
+    } else {
+        let credit_account = accounts.get(12 + host_accounts.len()).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        Some((credit_account, check_refund_credit(&escrow.refund_owner, credit_account, client_token_account)?))
+    };
+
+    let mut paid = 0u64;
+    let mut fees = 0u64;
+    for (i, triple) in host_accounts.chunks(3).enumerate() {
+        let (resource_account, host_token_account, staking_account) = (&triple[0], &triple[1], &triple[2]);
+        let assignment = &mut job.hosts[i];
+        if assignment.resource != *resource_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let resource_data = resource_account.try_borrow_data()?;
+        let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+        drop(resource_data);
+
+        if assignment.status == state::AssignmentStatus::Revealed && assignment.result_hash == agreed_hash {
+            let fee = (assignment.payment_amount as u128 * config.protocol_fee_bps as u128 / 10_000) as u64;
+            let host_token = unpack_token_account(host_token_account)?;
+            if host_token.owner.to_bytes() != assignment.host || host_token.mint.to_bytes() != job.mint {
+                return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+            }
+
+            let earnings = assignment.payment_amount - fee;
+            let delegator_cut = if pays_flux && resource.delegated_flux > 0 {
+                (earnings as u128 * resource.delegator_share_bps_at(now) as u128 / 10_000) as u64
+            } else {
+                0
+            };
+            if delegator_cut > 0 {
+                let (staking_pda, _staking_bump) = Pubkey::create_program_address(
+                    &[b"stake", resource_account.key.as_ref()],
+                    &pinocchio::program::ID,
+                )?;
+                if staking_account.key != &staking_pda {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                transfer_from_escrow(
+                    accounts,
+                    &escrow,
+                    escrow_account,
+                    vault_account,
+                    mint_account,
+                    staking_account,
+                    token_program,
+                    delegator_cut,
+                )?;
+                resource.delegated_flux += delegator_cut;
+            }
+
+            transfer_from_escrow(
+                accounts,
+                &escrow,
+                escrow_account,
+                vault_account,
+                mint_account,
+                host_token_account,
+                token_program,
+                earnings - delegator_cut,
+            )?;
+            paid += assignment.payment_amount;
+            fees += fee;
+            if let Some(pool) = pool.as_mut() {
+                pool.record_work(&mut resource, gpu_hours, now);
+                if delegator_cut > 0 {
+                    pool.sync_stake(&mut resource, now);
+                }
+            }
+            assignment.status = state::AssignmentStatus::Agreed;
+        } else {
+            assignment.status = state::AssignmentStatus::Disputed;
+        }
+
+        resource.release(job.requirements.gpu_count, job.requirements.vram_gb());
+        let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+        resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    }
+
+    let insurance_cut = if pays_flux {
+        (fees as u128 * config.insurance_fee_bps as u128 / 10_000) as u64
+    } else {
+        0
+    };
+    if insurance_cut > 0 {
+        transfer_from_escrow(
+            accounts,
+            &escrow,
+            escrow_account,
+            vault_account,
+            mint_account,
+            insurance_account,
+            token_program,
+            insurance_cut,
+        )?;
+    }
+    if fees > insurance_cut {
+        transfer_from_escrow(
+            accounts,
+            &escrow,
+            escrow_account,
+            vault_account,
+            mint_account,
+            fee_account,
+            token_program,
+            fees - insurance_cut,
+        )?;
+    }
+
+    let refund = escrow.amount.checked_sub(paid).ok_or(ProgramError::Custom(5))?; // InsufficientFunds
+    if refund > 0 {
+        transfer_from_escrow(
+            accounts,
+            &escrow,
+            escrow_account,
+            vault_account,
+            mint_account,
+            client_token_account,
+            token_program,
+            refund,
+        )?;
+    }
+    if let Some((credit_account, balance_before)) = refund_credit {
+        credit_refunds(credit_account, client_token_account, balance_before)?;
+    }
+
+    msg!("Settled job with quorum result");
+    escrow.status = state::EscrowStatus::Released;
+    let mut escrow_data_mut = escrow_account.try_borrow_mut_data()?;
+    escrow_data_mut.copy_from_slice(&escrow.try_to_vec()?);
+
+    if let Some(pool) = pool {
+        let mut pool_data_mut = reward_pool_account.try_borrow_mut_data()?;
+        pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+    }
+
+    job.result_hash = agreed_hash;
+    job.status = state::JobStatus::Completed;
+    job.capacity_released = true;
+    let mut job_data_mut = job_account.try_borrow_mut_data()?;
+    job_data_mut.copy_from_slice(&job.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{token_balance, transfer_checked};
+
+/// Operator adds FLUX to the fleet's pooled stake, which backs every member resource.
+pub fn stake_fleet(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let operator = &accounts[0];
+    let fleet_account = &accounts[1];
+    let token_account = &accounts[2];
+    let token_program = &accounts[3];
+    let staking_account = &accounts[4];
+    let mint_account = &accounts[5];
+
+    if !operator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let fleet_data = fleet_account.try_borrow_data()?;
+    let fleet = state::FleetAccount::try_from_slice(&fleet_data)?;
+    if fleet.operator != *operator.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    let (staking_pda, _bump) = Pubkey::create_program_address(
+        &[b"stake", fleet_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // Pooled stake is tracked by the vault balance, so a transfer fee is simply not staked.
+    transfer_checked(
+        accounts,
+        token_program,
+        token_account,
+        mint_account,
+        staking_account,
+        operator,
+        amount,
+        &[],
+    )?;
+
+    msg!("Staked FLUX to fleet pool");
+    Ok(())
+}
+
+/// Resolves the stake backing a resource: the fleet pool for fleet members, otherwise the
+/// resource's own stake including delegations and FLUX still unbonding. Returns the stake PDA's seed owner, its bump and
+/// the stake available.
+pub(crate) fn stake_pool(
+    resource_account: &AccountInfo,
+    resource: &state::ResourceAccount,
+    staking_account: &AccountInfo,
+) -> Result<(Pubkey, u8, u64), ProgramError> {
+    let in_fleet = resource.fleet != Pubkey::default();
+    let owner = if in_fleet { resource.fleet } else { *resource_account.key };
+
+    let (staking_pda, bump) = Pubkey::create_program_address(
+        &[b"stake", owner.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Pooled stake is tracked by the vault balance alone, since slashes through one member
+    // cannot update the others.
+    let available = if in_fleet {
+        token_balance(staking_account)?
+    } else {
+        resource.effective_stake() + resource.unbonding_flux
+    };
+    Ok((owner, bump, available))
+}
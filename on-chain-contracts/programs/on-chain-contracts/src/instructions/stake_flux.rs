@@ -1,23 +1,29 @@
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{AccountMeta, Instruction},
     msg,
-    program::invoke,
     program_error::ProgramError,
     pubkey::Pubkey,
     ProgramResult,
 };
 
 use crate::state;
+use super::deposit_escrow::{create_token_vault, transfer_checked};
+use super::initialize_config::load_config;
+use super::sync_rewards::sync_pool_stake;
 
-
-const SPL_TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
-
+/// Host stakes FLUX on a resource through either token program, creating the resource's
+/// staking account on the first stake. The stake grows by what the staking account received,
+/// net of any Token-2022 transfer fee.
 pub fn stake_flux(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let host = &accounts[0];
     let resource_account = &accounts[1];
     let token_account = &accounts[2];
     let token_program = &accounts[3];
+    let mint_account = &accounts[4];
+    let staking_account = &accounts[5];
+    let reward_pool_account = &accounts[6];
+    let system_program = &accounts[7];
+    let config_account = &accounts[8];
 
     if !host.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
@@ -29,36 +35,49 @@ pub fn stake_flux(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     if resource.host != *host.key {
         return Err(ProgramError::Custom(2));
     }
+    drop(resource_data);
 
     let (staking_pda, bump) = Pubkey::create_program_address(
         &[b"stake", resource_account.key.as_ref()],
         &pinocchio::program::ID,
     )?;
-    let transfer_ix = spl_token::instruction::transfer(
-        &SPL_TOKEN_PROGRAM_ID,
-        token_account.key,
-        &staking_pda,
-        host.key,
-        &[],
-        amount,
-    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
 
-    let transfer_instruction = Instruction {
-        program_id: *token_program.key,
-        accounts: vec![
-            AccountMeta::new(*token_account.key, false),
-            AccountMeta::new(staking_pda, false),
-            AccountMeta::new(*host.key, true),
-        ],
-        data: transfer_ix.data,
-    };
+    if staking_account.data_is_empty() {
+        let config = load_config(config_account)?;
+        if *mint_account.key != config.flux_mint {
+            return Err(ProgramError::Custom(54)); // MintMismatch
+        }
+        create_token_vault(
+            accounts,
+            host,
+            staking_account,
+            mint_account,
+            token_program,
+            system_program,
+            &staking_pda,
+            &[b"stake", resource_account.key.as_ref(), &[bump]],
+        )?;
+    }
 
-    invoke(&transfer_instruction, accounts)?;
+    let received = transfer_checked(
+        accounts,
+        token_program,
+        token_account,
+        mint_account,
+        staking_account,
+        host,
+        amount,
+        &[],
+    )?;
 
     msg!("Staking FLUX for host");
     let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
     let mut resource_mut = state::ResourceAccount::try_from_slice(&resource_data_mut)?;
-    resource_mut.staked_flux += amount;
+    resource_mut.staked_flux += received;
+    sync_pool_stake(reward_pool_account, &mut resource_mut)?;
     resource_data_mut.copy_from_slice(&resource_mut.try_to_vec()?);
 
     Ok(())
@@ -1,31 +1,103 @@
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{AccountMeta, Instruction},
-    program::invoke_signed,
+    msg,
     program_error::ProgramError,
     pubkey::Pubkey,
-    system_instruction,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 
 use crate::state;
+use super::deposit_escrow::{open_escrow, transfer_checked};
+use super::withdraw_credit::check_credit_vault;
+use super::utils::create_pda_account;
 
+/// The escrow was filled beforehand with `deposit_escrow`.
+pub const FUNDING_ESCROW: u8 = 0;
+/// The escrow is funded from the client's own credit vault.
+pub const FUNDING_CREDIT: u8 = 1;
+/// The escrow is funded from the vault of an organization the client is a member of.
+pub const FUNDING_ORG: u8 = 2;
 
+/// Starts a job on one or more resources. Additional resources run the same job redundantly;
+/// the client's escrow must cover `price_per_hour` per host. Hosts are paid in the escrow's
+/// mint at their price for it, or at their USD quote converted with the mint's price feed,
+/// which follows the config. With `funding` set to `FUNDING_CREDIT`, the escrow and its token
+/// vault are opened and funded in FLUX from the client's credit vault, passed after the feed
+/// as the credit account and its token vault, with the token program, the escrow vault and
+/// the FLUX mint. With `FUNDING_ORG` it is funded from an organization's vault instead,
+/// followed by the organization and the client's member record.
 pub fn start_job(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let client = &accounts[0];
     let job_account = &accounts[1];
     let resource_account = &accounts[2];
     let escrow_account = &accounts[3];
     let system_program = &accounts[4];
-
+    let config_account = &accounts[5];
+    let price_feed_account = &accounts[6];
 
     let job_id = u64::from_le_bytes(data[0..8].try_into().unwrap());
-    let specs = state::ResourceSpecs::try_from_slice(&data[8..])?;
+    let deadline = i64::from_le_bytes(data[8..16].try_into().unwrap());
+    let quorum = data[16];
+    let funding = data[17];
+    let requirements = state::JobRequirements::try_from_slice(&data[18..])?;
+
+    let redundant_resources = match funding {
+        FUNDING_ESCROW => &accounts[7..],
+        FUNDING_CREDIT => &accounts[12..],
+        FUNDING_ORG => &accounts[14..],
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
 
     if !client.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
+    let now = Clock::get()?.unix_timestamp;
+    if deadline <= now {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    if requirements.gpu_count == 0
+        || requirements.duration_hours == 0
+        || requirements.allowed_gpu_model_count as usize > state::MAX_ALLOWED_GPU_MODELS
+    {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let host_count = 1 + redundant_resources.len();
+    if host_count > state::MAX_HOSTS || quorum == 0 || quorum as usize > host_count {
+        return Err(ProgramError::Custom(13)); // InvalidQuorum
+    }
+
+    let (config_pda, _config_bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+
+    // Vault-funded jobs pay in FLUX; otherwise the escrow was already deposited in its mint.
+    // Only the client's own locked escrow for this job counts, not one listed on the order book.
+    let (mint, escrowed) = if funding == FUNDING_ESCROW {
+        let (escrow_pda, _escrow_bump) = Pubkey::create_program_address(
+            &[b"escrow", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
+            &pinocchio::program::ID,
+        )?;
+        if escrow_account.key != &escrow_pda {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let escrow_data = escrow_account.try_borrow_data()?;
+        let escrow = state::EscrowAccount::try_from_slice(&escrow_data)?;
+        if escrow.status != state::EscrowStatus::Locked || escrow.client != *client.key || escrow.job_id != job_id {
+            return Err(ProgramError::Custom(6)); // EscrowNotLocked
+        }
+        (escrow.mint, Some(escrow.amount))
+    } else {
+        (config.flux_mint, None)
+    };
+
     let (job_pda, bump) = Pubkey::create_program_address(
         &[b"job", client.key.as_ref(), job_id.to_le_bytes().as_ref()],
         &pinocchio::program::ID,
@@ -35,55 +107,242 @@ pub fn start_job(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let create_account_ix = system_instruction::create_account(
-        client.key,
-        job_account.key,
-        1000000,
-        state::JobAccount::SPACE as u64,
-        &pinocchio::program::ID,
-    );
-
-    let create_account_instruction = Instruction {
-        program_id: system_program.key,
-        accounts: vec![
-            AccountMeta::new(*client.key, true),
-            AccountMeta::new(*job_account.key, false),
-            AccountMeta::new(*system_program.key, false),
-        ],
-        data: create_account_ix.data,
-    };
-
-    invoke_signed(
-        &create_account_instruction,
+    create_pda_account(
         accounts,
-        &[&[b"job", client.key.as_ref(), job_id.to_le_bytes().as_ref(), &[bump]]],
+        client,
+        job_account,
+        system_program,
+        state::JobAccount::SPACE,
+        &pinocchio::program::ID,
+        &[b"job", client.key.as_ref(), job_id.to_le_bytes().as_ref(), &[bump]],
     )?;
 
-    let resource_data = resource_account.try_borrow_data()?;
-    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
-    if resource.status != state::ResourceStatus::Idle {
-        return Err(ProgramError::Custom(4));
+    let mut hosts = [state::HostAssignment::EMPTY; state::MAX_HOSTS];
+    let mut payment_amount = 0u64;
+    let resources = core::iter::once(resource_account).chain(redundant_resources.iter());
+    for (i, account) in resources.enumerate() {
+        let resource_data = account.try_borrow_data()?;
+        let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+        drop(resource_data);
+        if !resource.available_to(client.key, now, deadline) {
+            return Err(ProgramError::Custom(43)); // ResourceLeased
+        }
+        let price_per_hour = if resource.usd_price_per_hour > 0 {
+            load_price_feed(price_feed_account, &mint, now)?
+                .usd_to_tokens(resource.usd_price_per_hour)
+                .ok_or(ProgramError::Custom(1))? // InvalidPrice
+        } else {
+            resource
+                .price_in(&mint, &config.flux_mint)
+                .ok_or(ProgramError::Custom(54))? // MintMismatch
+        };
+        if !requirements.is_satisfied_by(&resource, &config) || !requirements.accepts_price(price_per_hour) {
+            return Err(ProgramError::Custom(34)); // RequirementsNotMet
+        }
+        if !resource.allocate(requirements.gpu_count, requirements.vram_gb()) {
+            return Err(ProgramError::Custom(33)); // InsufficientCapacity
+        }
+        // A host may only count once towards the quorum.
+        if hosts[..i].iter().any(|a| a.host == resource.host) {
+            return Err(ProgramError::Custom(13)); // InvalidQuorum
+        }
+
+        // The agreed price is the resource's own, never the client's.
+        let host_payment = requirements
+            .payment_at(price_per_hour)
+            .ok_or(ProgramError::Custom(1))?; // InvalidPrice
+        payment_amount = payment_amount.checked_add(host_payment).ok_or(ProgramError::Custom(1))?;
+
+        hosts[i] = state::HostAssignment {
+            host: resource.host,
+            resource: *account.key,
+            payment_amount: host_payment,
+            ..state::HostAssignment::EMPTY
+        };
+
+        let mut resource_data_mut = account.try_borrow_mut_data()?;
+        resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    }
+
+    // USD quotes make the total unknown until now, so a pre-filled escrow must cover it.
+    if escrowed.is_some_and(|amount| amount < payment_amount) {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    match funding {
+        FUNDING_CREDIT => fund_escrow_from_credit(accounts, client.key, job_id, hosts[0].host, payment_amount, mint)?,
+        FUNDING_ORG => {
+            let org_key = charge_org_budget(accounts, &hosts[..host_count], payment_amount, now)?;
+            fund_escrow_from_credit(accounts, &org_key, job_id, hosts[0].host, payment_amount, mint)?;
+        }
+        _ => {}
     }
 
     let mut job_data = state::JobAccount {
         job_id,
         client: *client.key,
-        host: *resource.host.key(),
         status: state::JobStatus::Active,
-        specs,
+        requirements,
+        hosts,
+        host_count: host_count as u8,
+        quorum,
         result_hash: [0; 32],
-        deadline: 0,
-        payment_amount: specs.price_per_hour,
+        deadline,
+        started_at: now,
+        payment_amount,
         escrow_account: *escrow_account.key,
+        audit_status: state::AuditStatus::Unchecked,
+        auditor: Pubkey::default(),
+        auditor_host: Pubkey::default(),
+        audit_deadline: 0,
+        quorum_slot: 0,
+        insurance_claimed: false,
+        insurable_loss: 0,
+        slashed_flux: 0,
+        mint,
+        capacity_released: false,
     };
 
     let mut account_data = job_account.try_borrow_mut_data()?;
     account_data.copy_from_slice(&job_data.try_to_vec()?);
 
-    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
-    let mut resource_mut = state::ResourceAccount::try_from_slice(&resource_data_mut)?;
-    resource_mut.status = state::ResourceStatus::Busy;
-    resource_data_mut.copy_from_slice(&resource_mut.try_to_vec()?);
+    Ok(())
+}
+
+/// Checks the client's membership, the organization's allowed hosts and both spending budgets,
+/// charging `amount` to the budgets. Returns the organization, whose vault pays for the job.
+fn charge_org_budget(accounts: &[AccountInfo], hosts: &[state::HostAssignment], amount: u64, now: i64) -> Result<Pubkey, ProgramError> {
+    let client = &accounts[0];
+    let org_account = &accounts[12];
+    let member_account = &accounts[13];
+
+    let org_data = org_account.try_borrow_data()?;
+    let mut org = state::OrgAccount::try_from_slice(&org_data)?;
+    drop(org_data);
+
+    let (member_pda, _member_bump) = Pubkey::create_program_address(
+        &[b"member", org_account.key.as_ref(), client.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if member_account.key != &member_pda || member_account.data_is_empty() {
+        return Err(ProgramError::Custom(45)); // NotOrgMember
+    }
+    let member_data = member_account.try_borrow_data()?;
+    let mut member = state::OrgMember::try_from_slice(&member_data)?;
+    drop(member_data);
+    if !member.active || member.org != *org_account.key {
+        return Err(ProgramError::Custom(45)); // NotOrgMember
+    }
+
+    if !hosts.iter().all(|a| org.allows_host(&a.host)) {
+        return Err(ProgramError::Custom(47)); // HostNotAllowed
+    }
+    if !member.budget.spend(amount, now, org.period_seconds) || !org.budget.spend(amount, now, org.period_seconds) {
+        return Err(ProgramError::Custom(46)); // SpendLimitExceeded
+    }
+
+    let mut org_data_mut = org_account.try_borrow_mut_data()?;
+    org_data_mut.copy_from_slice(&org.try_to_vec()?);
+    let mut member_data_mut = member_account.try_borrow_mut_data()?;
+    member_data_mut.copy_from_slice(&member.try_to_vec()?);
+
+    Ok(*org_account.key)
+}
+
+/// Opens the job escrow and moves `amount` of FLUX from `owner`'s credit vault into its token
+/// vault, locking it. Escrows funded by an organization refund into its credit vault.
+fn fund_escrow_from_credit(
+    accounts: &[AccountInfo],
+    owner: &Pubkey,
+    job_id: u64,
+    host: Pubkey,
+    amount: u64,
+    flux_mint: Pubkey,
+) -> ProgramResult {
+    let client = &accounts[0];
+    let escrow_account = &accounts[3];
+    let system_program = &accounts[4];
+    let credit_account = &accounts[7];
+    let credit_vault_account = &accounts[8];
+    let token_program = &accounts[9];
+    let vault_account = &accounts[10];
+    let mint_account = &accounts[11];
+
+    let (credit_pda, credit_bump) = Pubkey::create_program_address(
+        &[b"credit", owner.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if credit_account.key != &credit_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_credit_vault(credit_account, credit_vault_account)?;
+
+    if *mint_account.key != flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+
+    let credit_data = credit_account.try_borrow_data()?;
+    let mut credit = state::CreditAccount::try_from_slice(&credit_data)?;
+    drop(credit_data);
+    if credit.balance < amount {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    open_escrow(accounts, client, escrow_account, vault_account, mint_account, token_program, system_program, job_id)?;
+
+    // The job's payments are fixed, so the escrow must receive them in full.
+    let received = transfer_checked(
+        accounts,
+        token_program,
+        credit_vault_account,
+        mint_account,
+        vault_account,
+        credit_account,
+        amount,
+        &[&[b"credit", owner.as_ref(), &[credit_bump]]],
+    )?;
+    if received < amount {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    msg!("Funded escrow from credit");
+    credit.balance -= amount;
+    let mut credit_data_mut = credit_account.try_borrow_mut_data()?;
+    credit_data_mut.copy_from_slice(&credit.try_to_vec()?);
+
+    let escrow = state::EscrowAccount {
+        job_id,
+        client: *client.key,
+        host,
+        amount: received,
+        status: state::EscrowStatus::Locked,
+        mint: flux_mint,
+        // An organization's funds go back to its credit, a client's own to the client.
+        refund_owner: if owner == client.key { Pubkey::default() } else { *owner },
+    };
+    let mut escrow_data = escrow_account.try_borrow_mut_data()?;
+    escrow_data.copy_from_slice(&escrow.try_to_vec()?);
 
     Ok(())
 }
+
+/// Reads the USD price feed of `mint`, rejecting stale or uncertain prices.
+pub(crate) fn load_price_feed(feed_account: &AccountInfo, mint: &Pubkey, now: i64) -> Result<state::PriceFeed, ProgramError> {
+    let (feed_pda, _feed_bump) = Pubkey::create_program_address(
+        &[b"price_feed", mint.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if feed_account.key != &feed_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let feed_data = feed_account.try_borrow_data()?;
+    let feed = state::PriceFeed::try_from_slice(&feed_data)?;
+    if feed.is_stale(now) {
+        return Err(ProgramError::Custom(55)); // PriceStale
+    }
+    if !feed.is_confident() {
+        return Err(ProgramError::Custom(56)); // PriceUncertain
+    }
+    Ok(feed)
+}
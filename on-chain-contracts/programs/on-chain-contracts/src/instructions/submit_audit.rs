@@ -0,0 +1,68 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::stake_fleet::stake_pool;
+
+/// The selected auditor re-runs a sampled job and posts its own `result_hash`.
+pub fn submit_audit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let auditor_host = &accounts[0];
+    let job_account = &accounts[1];
+    let auditor_resource = &accounts[2];
+    let config_account = &accounts[3];
+
+    if !auditor_host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let result_hash: [u8; 32] = data[0..32].try_into().unwrap();
+
+    let account_data = job_account.try_borrow_data()?;
+    let mut job = state::JobAccount::try_from_slice(&account_data)?;
+    drop(account_data);
+    if job.audit_status != state::AuditStatus::Pending || job.auditor != *auditor_resource.key {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    if Clock::get()?.unix_timestamp > job.audit_deadline {
+        return Err(ProgramError::Custom(21)); // AuditWindowClosed
+    }
+
+    let resource_data = auditor_resource.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    if resource.host != *auditor_host.key {
+        return Err(ProgramError::Custom(2));
+    }
+
+    let (config_pda, _bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    let stake = if resource.fleet != Pubkey::default() {
+        let staking_account = accounts.get(4).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        stake_pool(auditor_resource, &resource, staking_account)?.2
+    } else {
+        resource.effective_stake()
+    };
+    if stake < config.min_auditor_stake {
+        return Err(ProgramError::Custom(19)); // InsufficientStake
+    }
+
+    job.auditor_host = *auditor_host.key;
+    job.audit_status = if job.quorum_hash() == Some(result_hash) {
+        state::AuditStatus::Passed
+    } else {
+        state::AuditStatus::Failed
+    };
+
+    let mut job_data_mut = job_account.try_borrow_mut_data()?;
+    job_data_mut.copy_from_slice(&job.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+pub fn submit_challenge_response(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let resource_account = &accounts[1];
+    let challenge_account = &accounts[2];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let output_hash: [u8; 32] = data[0..32].try_into().unwrap();
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    let now = Clock::get()?.unix_timestamp;
+    if !resource.can_operate(host.key, now) {
+        return Err(ProgramError::Custom(2));
+    }
+
+    let challenge_data = challenge_account.try_borrow_data()?;
+    let mut challenge = state::ChallengeAccount::try_from_slice(&challenge_data)?;
+    drop(challenge_data);
+    if challenge.resource != *resource_account.key || challenge.status != state::ChallengeStatus::Issued {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if now > challenge.deadline {
+        return Err(ProgramError::Custom(32)); // ChallengeWindowClosed
+    }
+
+    challenge.output_hash = output_hash;
+    challenge.status = state::ChallengeStatus::Answered;
+    let mut challenge_data_mut = challenge_account.try_borrow_mut_data()?;
+    challenge_data_mut.copy_from_slice(&challenge.try_to_vec()?);
+
+    Ok(())
+}
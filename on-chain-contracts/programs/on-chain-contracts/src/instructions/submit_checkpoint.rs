@@ -0,0 +1,90 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::add_operator::operated_assignment;
+use super::utils::create_pda_account;
+
+/// Appends an `(epoch, step, root)` checkpoint to the host's log for a running job,
+/// creating the log on the first submission. An operator submits on the host's behalf by
+/// passing the resource account after the system program.
+pub fn submit_checkpoint(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let host = &accounts[0];
+    let job_account = &accounts[1];
+    let checkpoint_account = &accounts[2];
+    let system_program = &accounts[3];
+
+    if !host.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let epoch = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let step = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let root: [u8; 32] = data[16..48].try_into().unwrap();
+
+    let job_data = job_account.try_borrow_data()?;
+    let job = state::JobAccount::try_from_slice(&job_data)?;
+    let index = operated_assignment(&job, host, accounts.get(4))?;
+    let assigned_host = job.hosts[index].host;
+    if job.status != state::JobStatus::Active {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+
+    let (checkpoint_pda, bump) = Pubkey::create_program_address(
+        &[b"checkpoints", job_account.key.as_ref(), assigned_host.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if checkpoint_account.key != &checkpoint_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut log = if checkpoint_account.data_is_empty() {
+        create_pda_account(
+            accounts,
+            host,
+            checkpoint_account,
+            system_program,
+            state::CheckpointAccount::SPACE,
+            &pinocchio::program::ID,
+            &[b"checkpoints", job_account.key.as_ref(), assigned_host.as_ref(), &[bump]],
+        )?;
+
+        state::CheckpointAccount {
+            job: *job_account.key,
+            host: assigned_host,
+            count: 0,
+            accepted: 0,
+            checkpoints: [state::Checkpoint::EMPTY; state::MAX_CHECKPOINTS],
+        }
+    } else {
+        let log_data = checkpoint_account.try_borrow_data()?;
+        state::CheckpointAccount::try_from_slice(&log_data)?
+    };
+
+    if let Some(latest) = log.latest() {
+        if (epoch, step) <= (latest.epoch, latest.step) {
+            return Err(ProgramError::Custom(25)); // CheckpointOutOfOrder
+        }
+    }
+    if log.count as usize == state::MAX_CHECKPOINTS {
+        return Err(ProgramError::Custom(26)); // CheckpointLogFull
+    }
+
+    log.checkpoints[log.count as usize] = state::Checkpoint {
+        epoch,
+        step,
+        root,
+        submitted_at: Clock::get()?.unix_timestamp,
+    };
+    log.count += 1;
+
+    let mut log_data_mut = checkpoint_account.try_borrow_mut_data()?;
+    log_data_mut.copy_from_slice(&log.try_to_vec()?);
+
+    Ok(())
+}
@@ -1,11 +1,16 @@
 use pinocchio::{
     account_info::AccountInfo,
     program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 
 use crate::state;
+use super::add_operator::operated_assignment;
 
+/// Commit phase of result submission: the host posts `hash(result_hash || salt || host)`
+/// so the bare result never appears on-chain before every host has committed. An operator
+/// commits on the host's behalf by passing the resource account after the job.
 pub fn submit_job_result(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let host = &accounts[0];
     let job_account = &accounts[1];
@@ -14,18 +19,25 @@ pub fn submit_job_result(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let result_hash: [u8; 32] = data.try_into().unwrap();
+    let commitment: [u8; 32] = data.try_into().unwrap();
+    if commitment == [0; 32] {
+        return Err(ProgramError::InvalidInstructionData);
+    }
 
     let account_data = job_account.try_borrow_data()?;
     let job = state::JobAccount::try_from_slice(&account_data)?;
-    if job.host != *host.key {
-        return Err(ProgramError::Custom(3));
+    let index = operated_assignment(&job, host, accounts.get(2))?;
+    if job.status != state::JobStatus::Active || job.hosts[index].status != state::AssignmentStatus::Assigned {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    if Clock::get()?.unix_timestamp > job.deadline {
+        return Err(ProgramError::Custom(10)); // CommitWindowClosed
     }
 
     let mut account_data_mut = job_account.try_borrow_mut_data()?;
     let mut job_mut = state::JobAccount::try_from_slice(&account_data_mut)?;
-    job_mut.result_hash = result_hash;
-    job_mut.status = state::JobStatus::Completed;
+    job_mut.hosts[index].result_commitment = commitment;
+    job_mut.hosts[index].status = state::AssignmentStatus::Committed;
     account_data_mut.copy_from_slice(&job_mut.try_to_vec()?);
 
     Ok(())
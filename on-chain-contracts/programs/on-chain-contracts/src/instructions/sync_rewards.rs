@@ -0,0 +1,57 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Permissionless crank: accrues a resource's rewards and re-weights it by its current stake.
+/// Stake changes re-weight the resource themselves; this is needed for resources last touched
+/// before the reward pool existed.
+pub fn sync_rewards(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let _cranker = &accounts[0];
+    let resource_account = &accounts[1];
+    let reward_pool_account = &accounts[2];
+
+    let mut pool = load_reward_pool(reward_pool_account)?.ok_or(ProgramError::UninitializedAccount)?;
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    pool.sync_stake(&mut resource, Clock::get()?.unix_timestamp);
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    let mut pool_data_mut = reward_pool_account.try_borrow_mut_data()?;
+    pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Loads the reward pool, or `None` until `initialize_rewards` has created it.
+pub(crate) fn load_reward_pool(reward_pool_account: &AccountInfo) -> Result<Option<state::RewardPool>, ProgramError> {
+    let (pool_pda, _pool_bump) = Pubkey::create_program_address(&[b"rewards"], &pinocchio::program::ID)?;
+    if reward_pool_account.key != &pool_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if reward_pool_account.data_is_empty() {
+        return Ok(None);
+    }
+
+    let pool_data = reward_pool_account.try_borrow_data()?;
+    Ok(Some(state::RewardPool::try_from_slice(&pool_data)?))
+}
+
+/// Re-weights a resource in the reward pool after its stake changed, so that it neither keeps
+/// earning on stake it lost nor misses out on stake it gained. Does nothing without a pool.
+pub(crate) fn sync_pool_stake(reward_pool_account: &AccountInfo, resource: &mut state::ResourceAccount) -> ProgramResult {
+    if let Some(mut pool) = load_reward_pool(reward_pool_account)? {
+        pool.sync_stake(resource, Clock::get()?.unix_timestamp);
+        let mut pool_data_mut = reward_pool_account.try_borrow_mut_data()?;
+        pool_data_mut.copy_from_slice(&pool.try_to_vec()?);
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::claim_lease::check_lease_vault;
+use super::deposit_escrow::{transfer_checked, unpack_token_account};
+use super::initialize_config::load_config;
+use super::stake_fleet::stake_pool;
+use super::sync_rewards::sync_pool_stake;
+
+/// Ends a lease early at the request of either party. The host is paid every period begun so
+/// far and the unused deposit is returned to the client, less `client_penalty_bps` paid to the
+/// host if the client terminates, or plus `host_penalty_bps` slashed from the host's stake if
+/// the host terminates. The slash is in FLUX and goes to the client's FLUX token account.
+pub fn terminate_lease(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let signer = &accounts[0];
+    let lease_account = &accounts[1];
+    let vault_account = &accounts[2];
+    let resource_account = &accounts[3];
+    let client_token_account = &accounts[4];
+    let host_token_account = &accounts[5];
+    let token_program = &accounts[6];
+    let staking_account = &accounts[7];
+    let reward_pool_account = &accounts[8];
+    let mint_account = &accounts[9];
+    let config_account = &accounts[10];
+    let flux_mint_account = &accounts[11];
+    let client_flux_account = &accounts[12];
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let lease_data = lease_account.try_borrow_data()?;
+    let mut lease = state::LeaseAccount::try_from_slice(&lease_data)?;
+    drop(lease_data);
+
+    let by_client = *signer.key == lease.client;
+    if !by_client && *signer.key != lease.host {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let now = Clock::get()?.unix_timestamp;
+    if lease.status != state::LeaseStatus::Active || now >= lease.end_at {
+        return Err(ProgramError::Custom(44)); // LeaseNotActive
+    }
+    if lease.resource != *resource_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_lease_vault(lease_account, vault_account)?;
+
+    let client_token = unpack_token_account(client_token_account)?;
+    if client_token.owner.to_bytes() != lease.client {
+        return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+    }
+    let host_token = unpack_token_account(host_token_account)?;
+    if host_token.owner.to_bytes() != lease.host {
+        return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    let started = lease.periods_started(now);
+    let owed = (started - lease.claimed_periods) as u64 * lease.rate_per_period;
+    let unused = (lease.total_periods() - started) as u64 * lease.rate_per_period;
+
+    let (_lease_pda, lease_bump) = Pubkey::create_program_address(
+        &[b"lease", lease.resource.as_ref(), lease.start_at.to_le_bytes().as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    let lease_seeds: &[&[u8]] = &[b"lease", lease.resource.as_ref(), &lease.start_at.to_le_bytes(), &[lease_bump]];
+
+    let (host_amount, client_amount) = if by_client {
+        let penalty = (unused as u128 * lease.client_penalty_bps as u128 / 10_000) as u64;
+        lease.status = state::LeaseStatus::TerminatedByClient;
+        (owed + penalty, unused - penalty)
+    } else {
+        let (stake_owner, staking_bump, stake) = stake_pool(resource_account, &resource, staking_account)?;
+
+        let penalty = ((unused as u128 * lease.host_penalty_bps as u128 / 10_000) as u64).min(stake);
+        if penalty > 0 {
+            let config = load_config(config_account)?;
+            if *flux_mint_account.key != config.flux_mint {
+                return Err(ProgramError::Custom(54)); // MintMismatch
+            }
+            let client_flux = unpack_token_account(client_flux_account)?;
+            if client_flux.owner.to_bytes() != lease.client {
+                return Err(ProgramError::Custom(9)); // InvalidTokenAccount
+            }
+            transfer_checked(
+                accounts,
+                token_program,
+                staking_account,
+                flux_mint_account,
+                client_flux_account,
+                staking_account,
+                penalty,
+                &[&[b"stake", stake_owner.as_ref(), &[staking_bump]]],
+            )?;
+            if resource.fleet == Pubkey::default() {
+                resource.slash(penalty);
+                sync_pool_stake(reward_pool_account, &mut resource)?;
+            }
+        }
+        lease.status = state::LeaseStatus::TerminatedByHost;
+        (owed, unused)
+    };
+
+    if host_amount > 0 {
+        transfer_checked(
+            accounts,
+            token_program,
+            vault_account,
+            mint_account,
+            host_token_account,
+            lease_account,
+            host_amount,
+            &[lease_seeds],
+        )?;
+    }
+    if client_amount > 0 {
+        transfer_checked(
+            accounts,
+            token_program,
+            vault_account,
+            mint_account,
+            client_token_account,
+            lease_account,
+            client_amount,
+            &[lease_seeds],
+        )?;
+    }
+
+    msg!("Terminated lease");
+    lease.claimed_periods = started;
+    let mut lease_data_mut = lease_account.try_borrow_mut_data()?;
+    lease_data_mut.copy_from_slice(&lease.try_to_vec()?);
+
+    resource.lessee = Pubkey::default();
+    resource.lease_start = 0;
+    resource.lease_end = 0;
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
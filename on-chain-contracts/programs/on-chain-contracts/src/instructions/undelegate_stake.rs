@@ -0,0 +1,66 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::sync_rewards::sync_pool_stake;
+
+/// Redeems delegation shares at their current value and starts unbonding the FLUX. Unbonding
+/// FLUX no longer counts as stake but stays slashable until it is withdrawn after
+/// `UNBONDING_PERIOD`; undelegating again restarts the period for the whole unbonding amount.
+pub fn undelegate_stake(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let delegator = &accounts[0];
+    let resource_account = &accounts[1];
+    let delegation_account = &accounts[2];
+    let reward_pool_account = &accounts[3];
+
+    if !delegator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let shares = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let delegation_data = delegation_account.try_borrow_data()?;
+    let mut delegation = state::DelegationAccount::try_from_slice(&delegation_data)?;
+    drop(delegation_data);
+    if delegation.delegator != *delegator.key || delegation.resource != *resource_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    // Shares from a generation wiped out by slashing are worth nothing
+    if delegation.generation != resource.delegation_generation || delegation.shares < shares {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    let amount = resource.value_of_shares(shares);
+    let unbonding_shares = resource.unbonding_shares_for(amount);
+    resource.delegation_shares -= shares;
+    resource.delegated_flux -= amount;
+    resource.unbonding_shares += unbonding_shares;
+    resource.unbonding_flux += amount;
+    sync_pool_stake(reward_pool_account, &mut resource)?;
+    delegation.shares -= shares;
+    // Unbonding shares left over from a generation wiped out by slashing are discarded first.
+    if delegation.unbonding_generation != resource.unbonding_generation {
+        delegation.unbonding_shares = 0;
+        delegation.unbonding_generation = resource.unbonding_generation;
+    }
+    delegation.unbonding_shares += unbonding_shares;
+    delegation.unbonding_ends_at = Clock::get()?.unix_timestamp + state::DelegationAccount::UNBONDING_PERIOD;
+
+    msg!("Unbonding delegated FLUX");
+    let mut delegation_data_mut = delegation_account.try_borrow_mut_data()?;
+    delegation_data_mut.copy_from_slice(&delegation.try_to_vec()?);
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
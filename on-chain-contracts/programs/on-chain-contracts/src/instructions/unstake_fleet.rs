@@ -0,0 +1,70 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::token_balance;
+
+/// Operator starts withdrawing FLUX from the fleet's pooled stake. The amount stays in the
+/// pool, and slashable, for `FleetAccount::UNBONDING_PERIOD` before `withdraw_fleet_unbonded`
+/// pays it out; unstaking again restarts the period for the whole unbonding amount. Every
+/// member resource follows the staking account, in fleet order, and none may be running a job.
+pub fn unstake_fleet(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let operator = &accounts[0];
+    let fleet_account = &accounts[1];
+    let staking_account = &accounts[2];
+    let member_accounts = &accounts[3..];
+
+    if !operator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let fleet_data = fleet_account.try_borrow_data()?;
+    let mut fleet = state::FleetAccount::try_from_slice(&fleet_data)?;
+    drop(fleet_data);
+    if fleet.operator != *operator.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+
+    if member_accounts.len() != fleet.resource_count as usize {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    for (member, member_account) in fleet.resources().iter().zip(member_accounts) {
+        if member != member_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let resource_data = member_account.try_borrow_data()?;
+        let resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+        // Running jobs stay backed by the pool until they settle or expire.
+        if resource.allocated_gpus > 0 {
+            return Err(ProgramError::Custom(4)); // InvalidJobStatus
+        }
+    }
+
+    let (staking_pda, _bump) = Pubkey::create_program_address(
+        &[b"stake", fleet_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let unbonding = fleet.unbonding_amount.checked_add(amount).ok_or(ProgramError::Custom(5))?; // InsufficientFunds
+    if token_balance(staking_account)? < unbonding {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    msg!("Unbonding FLUX from fleet pool");
+    fleet.unbonding_amount = unbonding;
+    fleet.unbonding_ends_at = Clock::get()?.unix_timestamp + state::FleetAccount::UNBONDING_PERIOD;
+    let mut fleet_data_mut = fleet_account.try_borrow_mut_data()?;
+    fleet_data_mut.copy_from_slice(&fleet.try_to_vec()?);
+
+    Ok(())
+}
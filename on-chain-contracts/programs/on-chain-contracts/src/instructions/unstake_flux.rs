@@ -1,22 +1,28 @@
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{AccountMeta, Instruction},
     msg,
-    program::invoke,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 
 use crate::state;
+use super::deposit_escrow::transfer_checked;
+use super::sync_rewards::sync_pool_stake;
 
-const SPL_TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
-
+/// Host withdraws FLUX staked on a resource through either token program. The stake shrinks by
+/// the full amount even if a Token-2022 transfer fee is withheld on the way out. Stake backing a
+/// running job or a current or upcoming lease cannot be withdrawn, so it stays slashable until
+/// the job settles or expires and the lease ends.
 pub fn unstake_flux(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let host = &accounts[0];
     let resource_account = &accounts[1];
     let token_account = &accounts[2];
     let token_program = &accounts[3];
+    let mint_account = &accounts[4];
+    let staking_account = &accounts[5];
+    let reward_pool_account = &accounts[6];
 
     if !host.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
@@ -29,36 +35,39 @@ pub fn unstake_flux(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     if resource.host != *host.key || resource.staked_flux < amount {
         return Err(ProgramError::Custom(5));
     }
+    if resource.allocated_gpus > 0 {
+        return Err(ProgramError::Custom(4)); // InvalidJobStatus
+    }
+    // Backs the penalty if the host terminates the lease early.
+    if resource.lease_end > Clock::get()?.unix_timestamp {
+        return Err(ProgramError::Custom(43)); // ResourceLeased
+    }
+    drop(resource_data);
 
     let (staking_pda, bump) = Pubkey::create_program_address(
         &[b"stake", resource_account.key.as_ref()],
         &pinocchio::program::ID,
     )?;
-    let transfer_ix = spl_token::instruction::transfer(
-        &SPL_TOKEN_PROGRAM_ID,
-        &staking_pda,
-        token_account.key,
-        host.key,
-        &[],
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    transfer_checked(
+        accounts,
+        token_program,
+        staking_account,
+        mint_account,
+        token_account,
+        staking_account,
         amount,
+        &[&[b"stake", resource_account.key.as_ref(), &[bump]]],
     )?;
 
-    let transfer_instruction = Instruction {
-        program_id: *token_program.key,
-        accounts: vec![
-            AccountMeta::new(staking_pda, false),
-            AccountMeta::new(*token_account.key, false),
-            AccountMeta::new(*host.key, true),
-        ],
-        data: transfer_ix.data,
-    };
-
-    invoke(&transfer_instruction, accounts)?;
-
     msg!("Unstaking FLUX for host");
     let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
     let mut resource_mut = state::ResourceAccount::try_from_slice(&resource_data_mut)?;
     resource_mut.staked_flux -= amount;
+    sync_pool_stake(reward_pool_account, &mut resource_mut)?;
     resource_data_mut.copy_from_slice(&resource_mut.try_to_vec()?);
 
     Ok(())
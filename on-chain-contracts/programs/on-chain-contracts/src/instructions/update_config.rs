@@ -0,0 +1,45 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::state;
+use super::initialize_config::parse_config;
+
+/// The admin replaces the config settings. `flux_mint` cannot change once set.
+pub fn update_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let config_account = &accounts[1];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (config_pda, _bump) = Pubkey::create_program_address(&[b"config"], &pinocchio::program::ID)?;
+    if config_account.key != &config_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let config_data = config_account.try_borrow_data()?;
+    let config = state::ConfigAccount::try_from_slice(&config_data)?;
+    drop(config_data);
+    if config.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+
+    let mut updated = parse_config(data)?;
+    // Stake, reward and insurance vaults already hold the FLUX mint.
+    if updated.flux_mint != config.flux_mint {
+        return Err(ProgramError::Custom(54)); // MintMismatch
+    }
+    updated.admin = config.admin;
+    updated.attester_count = config.attester_count;
+    updated.attesters = config.attesters;
+
+    let mut config_data_mut = config_account.try_borrow_mut_data()?;
+    config_data_mut.copy_from_slice(&updated.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Leaves the field unchanged.
+pub const FLEET_STATUS_UNCHANGED: u8 = 0;
+/// Takes every member Offline.
+pub const FLEET_STATUS_OFFLINE: u8 = 1;
+/// Brings every member back online.
+pub const FLEET_STATUS_ONLINE: u8 = 2;
+
+/// Operator reprices and/or changes the status of every fleet member at once, and refreshes
+/// the fleet's aggregate reputation. All member resources follow the fleet, in fleet order.
+pub fn update_fleet(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let operator = &accounts[0];
+    let fleet_account = &accounts[1];
+    let member_accounts = &accounts[2..];
+
+    if !operator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let price_per_hour = u64::from_le_bytes(data[0..8].try_into().unwrap()); // Zero keeps current prices
+    let status = data[8];
+    if status > FLEET_STATUS_ONLINE {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let fleet_data = fleet_account.try_borrow_data()?;
+    let mut fleet = state::FleetAccount::try_from_slice(&fleet_data)?;
+    drop(fleet_data);
+    if fleet.operator != *operator.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+    if member_accounts.len() != fleet.resource_count as usize {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut reputation_sum = 0u32;
+    for (account, key) in member_accounts.iter().zip(fleet.resources()) {
+        if account.key != key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let resource_data = account.try_borrow_data()?;
+        let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+        drop(resource_data);
+
+        if price_per_hour > 0 {
+            resource.specs.price_per_hour = price_per_hour;
+        }
+        match status {
+            FLEET_STATUS_OFFLINE => resource.status = state::ResourceStatus::Offline,
+            FLEET_STATUS_ONLINE => {
+                resource.status = state::ResourceStatus::Idle;
+                resource.refresh_status();
+            }
+            _ => {}
+        }
+        if status != FLEET_STATUS_UNCHANGED {
+            resource.last_updated = now;
+        }
+        reputation_sum += resource.reputation_score as u32;
+
+        let mut resource_data_mut = account.try_borrow_mut_data()?;
+        resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+    }
+
+    fleet.reputation_score = reputation_sum.checked_div(fleet.resource_count as u32).unwrap_or(0) as u16;
+    let mut fleet_data_mut = fleet_account.try_borrow_mut_data()?;
+    fleet_data_mut.copy_from_slice(&fleet.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::state;
+use super::create_org::apply_org_settings;
+
+/// Org admin changes the budget period, the org-wide limit and the allowed-host list.
+pub fn update_org(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let admin = &accounts[0];
+    let org_account = &accounts[1];
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let org_data = org_account.try_borrow_data()?;
+    let mut org = state::OrgAccount::try_from_slice(&org_data)?;
+    drop(org_data);
+    if org.admin != *admin.key {
+        return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+    }
+
+    apply_org_settings(&mut org, data)?;
+
+    let mut org_data_mut = org_account.try_borrow_mut_data()?;
+    org_data_mut.copy_from_slice(&org.try_to_vec()?);
+
+    Ok(())
+}
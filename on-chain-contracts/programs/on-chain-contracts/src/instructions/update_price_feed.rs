@@ -0,0 +1,43 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+
+/// Publisher posts a new USD price for a feed's mint, stamped with the current time.
+/// Data: `price (u64) | conf (u64) | expo (i32)`.
+pub fn update_price_feed(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let publisher = &accounts[0];
+    let feed_account = &accounts[1];
+
+    if !publisher.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let price = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let conf = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let expo = i32::from_le_bytes(data[16..20].try_into().unwrap());
+    if price == 0 {
+        return Err(ProgramError::Custom(1)); // InvalidPrice
+    }
+
+    let feed_data = feed_account.try_borrow_data()?;
+    let mut feed = state::PriceFeed::try_from_slice(&feed_data)?;
+    drop(feed_data);
+    if feed.publisher != *publisher.key {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    feed.price = price;
+    feed.conf = conf;
+    feed.expo = expo;
+    feed.publish_time = Clock::get()?.unix_timestamp;
+
+    let mut feed_data_mut = feed_account.try_borrow_mut_data()?;
+    feed_data_mut.copy_from_slice(&feed.try_to_vec()?);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use pinocchio::{
     account_info::AccountInfo,
     program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 
@@ -17,14 +18,21 @@ pub fn update_resource_status(accounts: &[AccountInfo], data: &[u8]) -> ProgramR
     }
     let account_data = resource_account.try_borrow_data()?;
     let resource = state::ResourceAccount::try_from_slice(&account_data)?;
-    if resource.host != *host.key {
+    let now = Clock::get()?.unix_timestamp;
+    if !resource.can_operate(host.key, now) {
         return Err(ProgramError::Custom(2));
     }
 
     let mut account_data_mut = resource_account.try_borrow_mut_data()?;
     let mut resource_mut = state::ResourceAccount::try_from_slice(&account_data_mut)?;
-    resource_mut.status = new_status;
-    resource_mut.last_updated = 0;  
+    // Hosts only toggle Offline; otherwise the status follows GPU utilization.
+    resource_mut.status = if new_status == state::ResourceStatus::Offline {
+        state::ResourceStatus::Offline
+    } else {
+        state::ResourceStatus::Idle
+    };
+    resource_mut.refresh_status();
+    resource_mut.last_updated = now;
     account_data_mut.copy_from_slice(&resource_mut.try_to_vec()?);
 
     Ok(())
@@ -0,0 +1,137 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+pub(crate) const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+
+/// Creates the PDA `account` with `space` bytes owned by `owner`, funded to the rent-exempt
+/// minimum from the Rent sysvar. A PDA that already holds lamports cannot be created with
+/// `create_account`, so it is topped up, allocated and assigned instead. Fails with
+/// `AccountAlreadyInitialized` if the PDA is already in use, and with `InsufficientFunds` if
+/// the payer cannot cover the rent.
+pub(crate) fn create_pda_account(
+    accounts: &[AccountInfo],
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    system_program: &AccountInfo,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    if *system_program.key != SYSTEM_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let required = Rent::get()?.minimum_balance(space);
+    let current = account.lamports();
+    if payer.lamports() < required.saturating_sub(current) {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    if current == 0 {
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            account.key,
+            required,
+            space as u64,
+            owner,
+        );
+
+        let create_account_instruction = Instruction {
+            program_id: *system_program.key,
+            accounts: vec![
+                AccountMeta::new(*payer.key, true),
+                AccountMeta::new(*account.key, true),
+            ],
+            data: create_account_ix.data,
+        };
+
+        return invoke_signed(&create_account_instruction, accounts, &[signer_seeds]);
+    }
+
+    // Anyone can send lamports to an address, so a PDA may be pre-funded before it is created.
+    if !account.data_is_empty() || !account.is_owned_by(&SYSTEM_PROGRAM_ID) {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    if required > current {
+        let transfer_ix = system_instruction::transfer(payer.key, account.key, required - current);
+
+        let transfer_instruction = Instruction {
+            program_id: *system_program.key,
+            accounts: vec![
+                AccountMeta::new(*payer.key, true),
+                AccountMeta::new(*account.key, false),
+            ],
+            data: transfer_ix.data,
+        };
+
+        invoke_signed(&transfer_instruction, accounts, &[])?;
+    }
+
+    let allocate_ix = system_instruction::allocate(account.key, space as u64);
+
+    let allocate_instruction = Instruction {
+        program_id: *system_program.key,
+        accounts: vec![AccountMeta::new(*account.key, true)],
+        data: allocate_ix.data,
+    };
+
+    invoke_signed(&allocate_instruction, accounts, &[signer_seeds])?;
+
+    let assign_ix = system_instruction::assign(account.key, owner);
+
+    let assign_instruction = Instruction {
+        program_id: *system_program.key,
+        accounts: vec![AccountMeta::new(*account.key, true)],
+        data: assign_ix.data,
+    };
+
+    invoke_signed(&assign_instruction, accounts, &[signer_seeds])
+}
+
+/// Moves lamports between two system-owned accounts. `from` must sign, or be a PDA of this
+/// program whose seeds are passed as `signer_seeds`.
+pub(crate) fn transfer_lamports(
+    accounts: &[AccountInfo],
+    from: &AccountInfo,
+    to: &AccountInfo,
+    system_program: &AccountInfo,
+    amount: u64,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    if *system_program.key != SYSTEM_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let transfer_ix = system_instruction::transfer(from.key, to.key, amount);
+
+    let transfer_instruction = Instruction {
+        program_id: *system_program.key,
+        accounts: vec![
+            AccountMeta::new(*from.key, true),
+            AccountMeta::new(*to.key, false),
+        ],
+        data: transfer_ix.data,
+    };
+
+    if signer_seeds.is_empty() {
+        invoke_signed(&transfer_instruction, accounts, &[])
+    } else {
+        invoke_signed(&transfer_instruction, accounts, &[signer_seeds])
+    }
+}
+
+/// Moves every lamport of a program-owned account to `destination` and closes it.
+pub(crate) fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    *destination.try_borrow_mut_lamports()? += account.lamports();
+    *account.try_borrow_mut_lamports()? = 0;
+    account.close()
+}
@@ -0,0 +1,69 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::attest_resource::verify_ed25519_signature;
+
+/// Settles an answered challenge against a verdict signed by a registered attester.
+/// A matching output refreshes `compute_rating`, both self-reported and attested; a mismatch
+/// penalizes the host. Verdicts are accepted until the challenge's verdict deadline.
+pub fn verify_challenge(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let _payer = &accounts[0];
+    let resource_account = &accounts[1];
+    let challenge_account = &accounts[2];
+    let attester_account = &accounts[3];
+    let instructions_sysvar = &accounts[4];
+
+    let verdict = state::ChallengeVerdict::try_from_slice(data)?;
+
+    let challenge_data = challenge_account.try_borrow_data()?;
+    let mut challenge = state::ChallengeAccount::try_from_slice(&challenge_data)?;
+    drop(challenge_data);
+    if challenge.resource != *resource_account.key
+        || challenge.status != state::ChallengeStatus::Answered
+        || verdict.resource != challenge.resource
+        || verdict.seed != challenge.seed
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if Clock::get()?.unix_timestamp > challenge.verdict_deadline() {
+        return Err(ProgramError::Custom(32)); // ChallengeWindowClosed
+    }
+
+    let attester_data = attester_account.try_borrow_data()?;
+    let attester = state::AttesterAccount::try_from_slice(&attester_data)?;
+    let (attester_pda, _bump) = Pubkey::create_program_address(
+        &[b"attester", attester.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if attester_account.key != &attester_pda || !attester.active {
+        return Err(ProgramError::Custom(28)); // InvalidAttestation
+    }
+
+    verify_ed25519_signature(instructions_sysvar, &attester.key, data)?;
+
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    let mut resource_mut = state::ResourceAccount::try_from_slice(&resource_data_mut)?;
+    if verdict.output_hash == challenge.output_hash {
+        challenge.status = state::ChallengeStatus::Passed;
+        resource_mut.specs.compute_rating = verdict.compute_rating;
+        resource_mut.attestation.compute_rating = verdict.compute_rating;
+    } else {
+        challenge.status = state::ChallengeStatus::Failed;
+        resource_mut.reputation_score = resource_mut
+            .reputation_score
+            .saturating_sub(state::ChallengeAccount::REPUTATION_PENALTY);
+        resource_mut.attested = false;
+    }
+    resource_data_mut.copy_from_slice(&resource_mut.try_to_vec()?);
+
+    let mut challenge_data_mut = challenge_account.try_borrow_mut_data()?;
+    challenge_data_mut.copy_from_slice(&challenge.try_to_vec()?);
+
+    Ok(())
+}
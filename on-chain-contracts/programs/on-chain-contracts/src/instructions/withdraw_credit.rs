@@ -0,0 +1,124 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{token_balance, transfer_checked};
+
+/// Returns unused credit from the credit's token vault to a token account of the owner's
+/// choosing. An organization's vault is withdrawn by its admin, with the organization account
+/// passed after the FLUX mint. Lamports above the credit account's rent, such as the rent of
+/// closed organization jobs, go to the withdrawing authority.
+pub fn withdraw_credit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let authority = &accounts[0];
+    let credit_account = &accounts[1];
+    let vault_account = &accounts[2];
+    let token_account = &accounts[3];
+    let token_program = &accounts[4];
+    let mint_account = &accounts[5];
+
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+    let credit_data = credit_account.try_borrow_data()?;
+    let mut credit = state::CreditAccount::try_from_slice(&credit_data)?;
+    drop(credit_data);
+
+    let (credit_pda, bump) = Pubkey::create_program_address(
+        &[b"credit", credit.client.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if credit_account.key != &credit_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_credit_vault(credit_account, vault_account)?;
+
+    if credit.client != *authority.key {
+        let org_account = accounts.get(6).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let org_data = org_account.try_borrow_data()?;
+        let org = state::OrgAccount::try_from_slice(&org_data)?;
+        if *org_account.key != credit.client || org.admin != *authority.key {
+            return Err(ProgramError::Custom(18)); // UnauthorizedAdmin
+        }
+    }
+
+    if credit.balance < amount {
+        return Err(ProgramError::Custom(5)); // InsufficientFunds
+    }
+
+    transfer_checked(
+        accounts,
+        token_program,
+        vault_account,
+        mint_account,
+        token_account,
+        credit_account,
+        amount,
+        &[&[b"credit", credit.client.as_ref(), &[bump]]],
+    )?;
+
+    msg!("Withdrew FLUX from credit");
+    credit.balance -= amount;
+    let mut credit_data_mut = credit_account.try_borrow_mut_data()?;
+    credit_data_mut.copy_from_slice(&credit.try_to_vec()?);
+    drop(credit_data_mut);
+
+    let surplus = credit_account
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(state::CreditAccount::SPACE));
+    if surplus > 0 {
+        *credit_account.try_borrow_mut_lamports()? -= surplus;
+        *authority.try_borrow_mut_lamports()? += surplus;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_credit_vault(credit_account: &AccountInfo, vault_account: &AccountInfo) -> ProgramResult {
+    let (vault_pda, _vault_bump) = Pubkey::create_program_address(
+        &[b"credit_vault", credit_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if vault_account.key != &vault_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Checks that `credit_account` is `owner`'s credit and `vault_account` its vault, which refunds
+/// of escrows funded from that credit are paid into. Returns the vault's balance before them.
+pub(crate) fn check_refund_credit(
+    owner: &Pubkey,
+    credit_account: &AccountInfo,
+    vault_account: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let (credit_pda, _credit_bump) = Pubkey::create_program_address(
+        &[b"credit", owner.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if credit_account.key != &credit_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_credit_vault(credit_account, vault_account)?;
+    token_balance(vault_account)
+}
+
+/// Adds whatever `vault_account` received since it held `balance_before` to the credit's balance.
+pub(crate) fn credit_refunds(credit_account: &AccountInfo, vault_account: &AccountInfo, balance_before: u64) -> ProgramResult {
+    let credit_data = credit_account.try_borrow_data()?;
+    let mut credit = state::CreditAccount::try_from_slice(&credit_data)?;
+    drop(credit_data);
+
+    credit.balance += token_balance(vault_account)? - balance_before;
+    let mut credit_data_mut = credit_account.try_borrow_mut_data()?;
+    credit_data_mut.copy_from_slice(&credit.try_to_vec()?);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::{token_balance, transfer_checked};
+
+/// Pays the operator the fleet's unbonded FLUX once the unbonding period has passed, less
+/// anything slashed from the pool in the meantime.
+pub fn withdraw_fleet_unbonded(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let operator = &accounts[0];
+    let fleet_account = &accounts[1];
+    let staking_account = &accounts[2];
+    let token_account = &accounts[3];
+    let token_program = &accounts[4];
+    let mint_account = &accounts[5];
+
+    if !operator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let fleet_data = fleet_account.try_borrow_data()?;
+    let mut fleet = state::FleetAccount::try_from_slice(&fleet_data)?;
+    drop(fleet_data);
+    if fleet.operator != *operator.key {
+        return Err(ProgramError::Custom(2)); // UnauthorizedHost
+    }
+    if fleet.unbonding_amount == 0 || Clock::get()?.unix_timestamp < fleet.unbonding_ends_at {
+        return Err(ProgramError::Custom(50)); // StillUnbonding
+    }
+
+    let (staking_pda, bump) = Pubkey::create_program_address(
+        &[b"stake", fleet_account.key.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let amount = fleet.unbonding_amount.min(token_balance(staking_account)?);
+
+    transfer_checked(
+        accounts,
+        token_program,
+        staking_account,
+        mint_account,
+        token_account,
+        staking_account,
+        amount,
+        &[&[b"stake", fleet_account.key.as_ref(), &[bump]]],
+    )?;
+
+    msg!("Withdrew unbonded FLUX from fleet pool");
+    fleet.unbonding_amount = 0;
+    let mut fleet_data_mut = fleet_account.try_borrow_mut_data()?;
+    fleet_data_mut.copy_from_slice(&fleet.try_to_vec()?);
+
+    Ok(())
+}
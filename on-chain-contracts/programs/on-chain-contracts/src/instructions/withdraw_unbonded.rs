@@ -0,0 +1,81 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::state;
+use super::deposit_escrow::transfer_checked;
+
+/// Pays out a delegation's unbonded FLUX once the unbonding period has passed, net of any
+/// slashes taken from the resource's unbonding pool in the meantime.
+pub fn withdraw_unbonded(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let delegator = &accounts[0];
+    let delegation_account = &accounts[1];
+    let resource_account = &accounts[2];
+    let staking_account = &accounts[3];
+    let token_account = &accounts[4];
+    let token_program = &accounts[5];
+    let mint_account = &accounts[6];
+
+    if !delegator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let delegation_data = delegation_account.try_borrow_data()?;
+    let mut delegation = state::DelegationAccount::try_from_slice(&delegation_data)?;
+    drop(delegation_data);
+    if delegation.delegator != *delegator.key || delegation.resource != *resource_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if delegation.unbonding_shares == 0 || Clock::get()?.unix_timestamp < delegation.unbonding_ends_at {
+        return Err(ProgramError::Custom(50)); // StillUnbonding
+    }
+
+    let (staking_pda, bump) = Pubkey::create_program_address(
+        &[b"stake", delegation.resource.as_ref()],
+        &pinocchio::program::ID,
+    )?;
+    if staking_account.key != &staking_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let resource_data = resource_account.try_borrow_data()?;
+    let mut resource = state::ResourceAccount::try_from_slice(&resource_data)?;
+    drop(resource_data);
+
+    // Unbonding shares from a generation wiped out by slashing are worth nothing.
+    let amount = if delegation.unbonding_generation == resource.unbonding_generation {
+        let amount = resource.value_of_unbonding_shares(delegation.unbonding_shares);
+        resource.unbonding_shares -= delegation.unbonding_shares;
+        resource.unbonding_flux -= amount;
+        amount
+    } else {
+        0
+    };
+
+    if amount > 0 {
+        transfer_checked(
+            accounts,
+            token_program,
+            staking_account,
+            mint_account,
+            token_account,
+            staking_account,
+            amount,
+            &[&[b"stake", delegation.resource.as_ref(), &[bump]]],
+        )?;
+    }
+
+    msg!("Withdrew unbonded FLUX");
+    delegation.unbonding_shares = 0;
+    let mut delegation_data_mut = delegation_account.try_borrow_mut_data()?;
+    delegation_data_mut.copy_from_slice(&delegation.try_to_vec()?);
+    let mut resource_data_mut = resource_account.try_borrow_mut_data()?;
+    resource_data_mut.copy_from_slice(&resource.try_to_vec()?);
+
+    Ok(())
+}
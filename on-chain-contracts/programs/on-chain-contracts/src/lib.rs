@@ -26,11 +26,75 @@ pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data:
         1 => instructions::update_resource_status(accounts, rest),
         2 => instructions::start_job(accounts, rest),
         3 => instructions::submit_job_result(accounts, rest),
-        4 => instructions::resolve_job(accounts, rest),
         5 => instructions::deposit_escrow(accounts, rest),
         6 => instructions::release_payment(accounts, rest),
         7 => instructions::stake_flux(accounts, rest), 
         8 => instructions::unstake_flux(accounts, rest), 
+        9 => instructions::expire_job(accounts, rest),
+        10 => instructions::reveal_job_result(accounts, rest),
+        11 => instructions::settle_job(accounts, rest),
+        12 => instructions::initialize_config(accounts, rest),
+        13 => instructions::update_config(accounts, rest),
+        14 => instructions::join_auditor_pool(accounts, rest),
+        15 => instructions::leave_auditor_pool(accounts, rest),
+        16 => instructions::submit_audit(accounts, rest),
+        17 => instructions::sample_audit(accounts, rest),
+        18 => instructions::submit_checkpoint(accounts, rest),
+        19 => instructions::accept_checkpoint(accounts, rest),
+        20 => instructions::register_attester(accounts, rest),
+        21 => instructions::revoke_attester(accounts, rest),
+        22 => instructions::attest_resource(accounts, rest),
+        23 => instructions::issue_challenge(accounts, rest),
+        24 => instructions::submit_challenge_response(accounts, rest),
+        25 => instructions::verify_challenge(accounts, rest),
+        26 => instructions::expire_challenge(accounts, rest),
+        27 => instructions::post_job(accounts, rest),
+        28 => instructions::place_bid(accounts, rest),
+        29 => instructions::accept_bid(accounts, rest),
+        30 => instructions::award_job(accounts, rest),
+        31 => instructions::drop_bid(accounts, rest),
+        32 => instructions::create_order_book(accounts, rest),
+        33 => instructions::post_ask(accounts, rest),
+        34 => instructions::post_bid(accounts, rest),
+        35 => instructions::cancel_order(accounts, rest),
+        36 => instructions::match_orders(accounts, rest),
+        37 => instructions::create_lease(accounts, rest),
+        38 => instructions::claim_lease(accounts, rest),
+        39 => instructions::terminate_lease(accounts, rest),
+        40 => instructions::deposit_credit(accounts, rest),
+        41 => instructions::withdraw_credit(accounts, rest),
+        42 => instructions::create_org(accounts, rest),
+        43 => instructions::update_org(accounts, rest),
+        44 => instructions::set_org_member(accounts, rest),
+        45 => instructions::add_operator(accounts, rest),
+        46 => instructions::revoke_operator(accounts, rest),
+        47 => instructions::heartbeat(accounts, rest),
+        48 => instructions::create_fleet(accounts, rest),
+        49 => instructions::add_fleet_resource(accounts, rest),
+        50 => instructions::remove_fleet_resource(accounts, rest),
+        51 => instructions::stake_fleet(accounts, rest),
+        52 => instructions::unstake_fleet(accounts, rest),
+        53 => instructions::update_fleet(accounts, rest),
+        54 => instructions::withdraw_fleet_unbonded(accounts, rest),
+        55 => instructions::set_delegator_share(accounts, rest),
+        56 => instructions::delegate_stake(accounts, rest),
+        57 => instructions::undelegate_stake(accounts, rest),
+        58 => instructions::withdraw_unbonded(accounts, rest),
+        59 => instructions::initialize_rewards(accounts, rest),
+        60 => instructions::fund_rewards(accounts, rest),
+        61 => instructions::advance_epoch(accounts, rest),
+        62 => instructions::sync_rewards(accounts, rest),
+        63 => instructions::claim_rewards(accounts, rest),
+        64 => instructions::compound_rewards(accounts, rest),
+        65 => instructions::claim_insurance(accounts, rest),
+        66 => instructions::initialize_insurance(accounts, rest),
+        67 => instructions::set_mint_price(accounts, rest),
+        68 => instructions::init_price_feed(accounts, rest),
+        69 => instructions::update_price_feed(accounts, rest),
+        70 => instructions::set_usd_price(accounts, rest),
+        71 => instructions::close_job(accounts, rest),
+        72 => instructions::close_bid(accounts, rest),
+        73 => instructions::close_lease(accounts, rest),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
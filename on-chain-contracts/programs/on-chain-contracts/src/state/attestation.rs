@@ -0,0 +1,44 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// A benchmarking service allowed to attest host hardware.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct AttesterAccount {
+    pub key: Pubkey, // Ed25519 key the attester signs statements with
+    pub active: bool,
+}
+
+impl AttesterAccount {
+    pub const SPACE: usize = 32 + 1;
+}
+
+/// Message signed by an attester; its borsh encoding is the Ed25519 message.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct AttestationStatement {
+    pub resource: Pubkey,
+    pub gpu_model: String,
+    pub vram_gb: u8,
+    pub cpu_cores: u8,
+    pub compute_rating: u32,
+    pub issued_at: i64,
+}
+
+impl AttestationStatement {
+    /// Seconds a statement stays valid after `issued_at`.
+    pub const MAX_AGE: i64 = 24 * 3600;
+}
+
+/// Measured specs recorded on a resource when an attestation is accepted.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Default)]
+pub struct AttestedSpecs {
+    pub gpu_model: String,
+    pub vram_gb: u8,
+    pub cpu_cores: u8,
+    pub compute_rating: u32,
+    pub attester: Pubkey,
+    pub attested_at: i64,
+}
+
+impl AttestedSpecs {
+    pub const SPACE: usize = 4 + 20 + 1 + 1 + 4 + 32 + 8;
+}
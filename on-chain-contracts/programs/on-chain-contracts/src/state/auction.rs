@@ -0,0 +1,35 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct AuctionAccount {
+    pub job: Pubkey,
+    pub bidding_ends_at: i64,
+    pub bid_count: u32,
+    pub best_bid: Pubkey, // Bid account with the lowest score so far
+    pub best_score: u64,
+}
+
+impl AuctionAccount {
+    pub const SPACE: usize = 32 + 8 + 4 + 32 + 8;
+
+    /// Reputation-weighted bid score; lower wins. A host with twice the reputation
+    /// can charge twice the price for the same score.
+    pub fn score(price_per_hour: u64, reputation_score: u16) -> u64 {
+        (price_per_hour as u128 * 10_000 / reputation_score.max(1) as u128).min(u64::MAX as u128) as u64
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct BidAccount {
+    pub job: Pubkey,
+    pub resource: Pubkey,
+    pub host: Pubkey,
+    pub price_per_hour: u64, // Per GPU
+    pub placed_at: i64,
+    pub score: u64, // Reputation-weighted score when placed
+}
+
+impl BidAccount {
+    pub const SPACE: usize = 32 + 32 + 32 + 8 + 8 + 8;
+}
@@ -0,0 +1,34 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Maximum number of resources enrolled as spot-check auditors.
+pub const MAX_AUDITORS: usize = 64;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct AuditorPool {
+    pub count: u8,
+    pub resources: [Pubkey; MAX_AUDITORS], // Only the first `count` entries are used
+    pub hosts: [Pubkey; MAX_AUDITORS], // Host of each enrolled resource
+}
+
+impl AuditorPool {
+    pub const SPACE: usize = 1 + 32 * MAX_AUDITORS + 32 * MAX_AUDITORS;
+
+    pub fn members(&self) -> &[Pubkey] {
+        &self.resources[..self.count as usize]
+    }
+
+    /// Picks a pool member from `seed`, skipping every resource run by a host in `excluded_hosts`
+    /// so that no host audits its own job through another of its resources.
+    pub fn select(&self, seed: &[u8; 32], excluded_hosts: &[Pubkey]) -> Option<Pubkey> {
+        let count = self.count as usize;
+        if count == 0 {
+            return None;
+        }
+        let start = u64::from_le_bytes(seed[8..16].try_into().unwrap()) as usize % count;
+        (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|&i| !excluded_hosts.contains(&self.hosts[i]))
+            .map(|i| self.resources[i])
+    }
+}
@@ -0,0 +1,61 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ChallengeAccount {
+    pub resource: Pubkey,
+    pub nonce: u64, // Number of challenges issued to the resource
+    pub seed: [u8; 32], // Input the benchmark must be run on
+    pub issued_at: i64,
+    pub deadline: i64,
+    pub output_hash: [u8; 32], // Benchmark output posted by the host
+    pub status: ChallengeStatus,
+}
+
+impl ChallengeAccount {
+    pub const SPACE: usize = 32 + 8 + 32 + 8 + 8 + 32 + 1;
+
+    /// Seconds the host has to post the benchmark output.
+    pub const RESPONSE_WINDOW: i64 = 600;
+
+    /// Seconds after the response deadline an attester has to settle an answered challenge.
+    pub const VERDICT_WINDOW: i64 = 24 * 3600;
+
+    /// Minimum seconds between two challenges to the same resource.
+    pub const INTERVAL: i64 = 7 * 24 * 3600;
+
+    /// Reputation points removed when a challenge is failed or missed.
+    pub const REPUTATION_PENALTY: u16 = 200;
+
+    /// An answered challenge stops blocking new ones once its verdict window has passed.
+    pub fn is_open(&self, now: i64) -> bool {
+        match self.status {
+            ChallengeStatus::Issued => true,
+            ChallengeStatus::Answered => now <= self.verdict_deadline(),
+            _ => false,
+        }
+    }
+
+    pub fn verdict_deadline(&self) -> i64 {
+        self.deadline + Self::VERDICT_WINDOW
+    }
+}
+
+/// Verifier verdict on a challenge; its borsh encoding is the Ed25519 message.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ChallengeVerdict {
+    pub resource: Pubkey,
+    pub seed: [u8; 32],
+    pub output_hash: [u8; 32], // Expected benchmark output for the seed
+    pub compute_rating: u32, // Rating measured from the benchmark run
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ChallengeStatus {
+    Issued,   // Waiting for the host's output
+    Answered, // Output posted, waiting for the verifier
+    Passed,   // Output confirmed by the verifier
+    Failed,   // Output rejected by the verifier
+    TimedOut, // Host did not answer in time
+    Unverified, // No verdict arrived in time; the host is not penalized
+}
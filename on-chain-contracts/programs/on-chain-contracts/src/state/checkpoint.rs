@@ -0,0 +1,76 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+use anchor_lang::solana_program::hash::hashv;
+
+/// Maximum number of checkpoints a host can record for one job.
+pub const MAX_CHECKPOINTS: usize = 64;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct CheckpointAccount {
+    pub job: Pubkey,
+    pub host: Pubkey,
+    pub count: u16,
+    pub accepted: u16, // Checkpoints the client has verified, counted from the first
+    pub checkpoints: [Checkpoint; MAX_CHECKPOINTS], // Only the first `count` entries are used
+}
+
+impl CheckpointAccount {
+    pub const SPACE: usize = 32 + 32 + 2 + 2 + Checkpoint::SPACE * MAX_CHECKPOINTS;
+
+    pub fn entries(&self) -> &[Checkpoint] {
+        &self.checkpoints[..self.count as usize]
+    }
+
+    pub fn latest(&self) -> Option<&Checkpoint> {
+        self.entries().last()
+    }
+
+    /// Latest checkpoint the client has verified, which sizes the host's partial payment.
+    pub fn latest_accepted(&self) -> Option<&Checkpoint> {
+        self.entries()[..self.accepted as usize].last()
+    }
+
+    /// Share of `payment` earned by the accepted progress of a job that ran from `started_at`
+    /// to `deadline`, in proportion to the time the accepted checkpoint covers.
+    pub fn partial_payment(&self, payment: u64, started_at: i64, deadline: i64) -> u64 {
+        let Some(checkpoint) = self.latest_accepted() else {
+            return 0;
+        };
+        if deadline <= started_at {
+            return 0;
+        }
+        let covered = (checkpoint.submitted_at - started_at).clamp(0, deadline - started_at);
+        (payment as u128 * covered as u128 / (deadline - started_at) as u128) as u64
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+pub struct Checkpoint {
+    pub epoch: u64,
+    pub step: u64,
+    pub root: [u8; 32], // Merkle root of the intermediate outputs
+    pub submitted_at: i64,
+}
+
+impl Checkpoint {
+    pub const SPACE: usize = 8 + 8 + 32 + 8;
+
+    pub const EMPTY: Checkpoint = Checkpoint {
+        epoch: 0,
+        step: 0,
+        root: [0; 32],
+        submitted_at: 0,
+    };
+
+    /// Verifies that `leaf` is included under `root`, hashing each pair in sorted order.
+    pub fn verify(&self, leaf: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+        let computed = proof.iter().fold(*leaf, |node, sibling| {
+            if node <= *sibling {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+        computed == self.root
+    }
+}
@@ -0,0 +1,65 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Maximum number of payment mints accepted besides FLUX.
+pub const MAX_PAYMENT_MINTS: usize = 4;
+
+/// Maximum number of attesters registered at once.
+pub const MAX_ATTESTERS: usize = 16;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ConfigAccount {
+    pub admin: Pubkey,
+    pub treasury: Pubkey, // FLUX token account receiving protocol fees
+    pub protocol_fee_bps: u16, // Fee withheld from each host payment
+    pub audit_rate_bps: u16, // Share of settled results sampled for a spot-check audit
+    pub min_auditor_stake: u64, // Stake a resource needs to join the auditor pool
+    pub insurance_fee_bps: u16, // Share of each protocol fee paid into the insurance pool
+    pub insurance_slash_bps: u16, // Share of each expiry slash paid into the insurance pool
+    pub insurance_cover_bps: u16, // Share of a failed job's payment a client can claim from the pool
+    pub insurance_max_payout: u64, // Cap on a single insurance claim
+    pub flux_mint: Pubkey, // Staking token, always accepted for payment with fees paid to `treasury`
+    pub payment_mint_count: u8,
+    pub payment_mints: [PaymentMint; MAX_PAYMENT_MINTS], // Other accepted mints, only the first `payment_mint_count` are used
+    pub attester_count: u8,
+    pub attesters: [Pubkey; MAX_ATTESTERS], // Attesters whose statements count, only the first `attester_count` are used
+}
+
+impl ConfigAccount {
+    pub const SPACE: usize = 32 + 32 + 2 + 2 + 8 + 2 + 2 + 2 + 8 + 32 + 1 + PaymentMint::SPACE * MAX_PAYMENT_MINTS + 1 + 32 * MAX_ATTESTERS;
+
+    pub fn payment_mints(&self) -> &[PaymentMint] {
+        &self.payment_mints[..self.payment_mint_count as usize]
+    }
+
+    pub fn accepts_mint(&self, mint: &Pubkey) -> bool {
+        self.treasury_for(mint).is_some()
+    }
+
+    /// Token account receiving protocol fees paid in `mint`, if the mint is accepted.
+    pub fn treasury_for(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.flux_mint {
+            return Some(self.treasury);
+        }
+        self.payment_mints().iter().find(|p| p.mint == *mint).map(|p| p.treasury)
+    }
+
+    pub fn is_attester_active(&self, attester: &Pubkey) -> bool {
+        self.attesters[..self.attester_count as usize].contains(attester)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    pub treasury: Pubkey, // Token account of this mint receiving protocol fees
+}
+
+impl PaymentMint {
+    pub const SPACE: usize = 32 + 32;
+
+    pub const EMPTY: PaymentMint = PaymentMint {
+        mint: [0; 32],
+        treasury: [0; 32],
+    };
+}
@@ -0,0 +1,13 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Prepaid client balance, held by the credit token vault, that `start_job` can draw job escrow from.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct CreditAccount {
+    pub client: Pubkey,
+    pub balance: u64,
+}
+
+impl CreditAccount {
+    pub const SPACE: usize = 32 + 8;
+}
@@ -0,0 +1,21 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// A token holder's stake delegated to a resource.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct DelegationAccount {
+    pub resource: Pubkey,
+    pub delegator: Pubkey,
+    pub shares: u64, // Claim on the resource's `delegated_flux`
+    pub generation: u64, // Resource delegation generation `shares` belong to
+    pub unbonding_shares: u64, // Claim on the resource's `unbonding_flux`
+    pub unbonding_generation: u64, // Resource unbonding generation `unbonding_shares` belong to
+    pub unbonding_ends_at: i64,
+}
+
+impl DelegationAccount {
+    pub const SPACE: usize = 32 + 32 + 8 + 8 + 8 + 8 + 8;
+
+    /// Seconds undelegated FLUX stays locked before it can be withdrawn.
+    pub const UNBONDING_PERIOD: i64 = 7 * 24 * 3600;
+}
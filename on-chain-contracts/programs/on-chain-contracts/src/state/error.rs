@@ -7,4 +7,57 @@ pub enum FluxError {
     InsufficientFunds,
     EscrowNotLocked,
     ProposalNotActive,
+    JobNotExpired,
+    InvalidTokenAccount,
+    CommitWindowClosed,
+    RevealWindowClosed,
+    CommitmentMismatch,
+    InvalidQuorum,
+    RevealNotOpen,
+    QuorumNotReached,
+    SettlementNotReady,
+    QuorumReached,
+    UnauthorizedAdmin,
+    InsufficientStake,
+    AuditorPoolFull,
+    AuditWindowClosed,
+    AuditPending,
+    AuditFailed,
+    AuditNotReady,
+    CheckpointOutOfOrder,
+    CheckpointLogFull,
+    InvalidCheckpointProof,
+    InvalidAttestation,
+    AttestationExpired,
+    AttestersFull,
+    ChallengeNotDue,
+    ChallengeWindowClosed,
+    InsufficientCapacity,
+    RequirementsNotMet,
+    BiddingClosed,
+    BiddingOpen,
+    NoBids,
+    JobDeadlinePassed,
+    BidStillAwardable,
+    OrdersNotCrossed,
+    OrderBookFull,
+    OrderNotFound,
+    ResourceLeased,
+    LeaseNotActive,
+    NotOrgMember,
+    SpendLimitExceeded,
+    HostNotAllowed,
+    OperatorSlotsFull,
+    FleetFull,
+    StillUnbonding,
+    EpochNotOver,
+    InsuranceClaimed,
+    MintNotAllowed,
+    MintMismatch,
+    PriceStale,
+    PriceUncertain,
+    EscrowStillLocked,
+    DisputeWindowOpen,
+    CapacityNotReleased,
+    LeaseStillActive,
 }
@@ -6,12 +6,14 @@ pub struct EscrowAccount {
     pub job_id: u64,
     pub client: Pubkey,
     pub host: Pubkey,
-    pub amount: u64, // FLUX tokens held
+    pub amount: u64, // Tokens held
     pub status: EscrowStatus,
+    pub mint: Pubkey, // Payment mint of the held tokens
+    pub refund_owner: Pubkey, // Credit owner refunded through its vault, default to refund the client
 }
 
 impl EscrowAccount {
-    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1 + 32 + 32;
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
@@ -19,4 +21,5 @@ pub enum EscrowStatus {
     Locked,    // Funds held until job completion
     Released,  // Funds transferred to host
     Refunded,  // Funds returned to client
+    Listed,    // Backing a resting order-book bid, unusable for anything else
 }
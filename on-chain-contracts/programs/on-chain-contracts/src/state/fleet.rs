@@ -0,0 +1,29 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Maximum number of resources in one fleet.
+pub const MAX_FLEET_RESOURCES: usize = 64;
+
+/// Group of resources run by one operator, backed by a shared stake pool at
+/// `[b"stake", fleet]` in place of each member's own stake.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct FleetAccount {
+    pub operator: Pubkey, // Host key owning every member resource
+    pub fleet_id: u64,
+    pub reputation_score: u16, // Average member reputation as of the last fleet update
+    pub resource_count: u8,
+    pub resources: [Pubkey; MAX_FLEET_RESOURCES], // Only the first `resource_count` are used
+    pub unbonding_amount: u64, // Pooled FLUX the operator is withdrawing, still slashable until released
+    pub unbonding_ends_at: i64,
+}
+
+impl FleetAccount {
+    pub const SPACE: usize = 32 + 8 + 2 + 1 + 32 * MAX_FLEET_RESOURCES + 8 + 8;
+
+    /// Seconds unstaked pool FLUX stays locked, and slashable, before it can be withdrawn.
+    pub const UNBONDING_PERIOD: i64 = 7 * 24 * 3600;
+
+    pub fn resources(&self) -> &[Pubkey] {
+        &self.resources[..self.resource_count as usize]
+    }
+}
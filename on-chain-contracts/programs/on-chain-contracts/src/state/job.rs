@@ -1,29 +1,206 @@
 use pinocchio::pubkey::Pubkey;
 use borsh::{BorshDeserialize, BorshSerialize};
+use anchor_lang::solana_program::hash::hashv;
 
-use super::resource::ResourceSpecs;
+use super::config::ConfigAccount;
+use super::resource::ResourceAccount;
+
+/// Maximum number of hosts a job can be redundantly assigned to.
+pub const MAX_HOSTS: usize = 5;
+
+/// Maximum number of GPU models a job can restrict itself to.
+pub const MAX_ALLOWED_GPU_MODELS: usize = 4;
+
+/// Maximum length in bytes of a GPU model name.
+pub const MAX_GPU_MODEL_LEN: usize = 20;
 
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct JobAccount {
     pub job_id: u64,
     pub client: Pubkey,
-    pub host: Pubkey,
     pub status: JobStatus,
-    pub specs: ResourceSpecs, // Copy of the specs for the job
-    pub result_hash: [u8; 32],
+    pub requirements: JobRequirements,
+    pub hosts: [HostAssignment; MAX_HOSTS], // Only the first `host_count` entries are used
+    pub host_count: u8,
+    pub quorum: u8, // Identical results required before payment
+    pub result_hash: [u8; 32], // Result agreed by the quorum
     pub deadline: i64,
-    pub payment_amount: u64,
+    pub started_at: i64, // When the job became `Active`
+    pub payment_amount: u64, // Total payment across all assigned hosts
     pub escrow_account: Pubkey,
+    pub audit_status: AuditStatus,
+    pub auditor: Pubkey, // Auditor resource drawn from the pool, if sampled
+    pub auditor_host: Pubkey, // Host that submitted the audit
+    pub audit_deadline: i64,
+    pub quorum_slot: u64, // Slot of the reveal that completed the quorum, 0 until then
+    pub insurance_claimed: bool, // Client has claimed insurance for the failed job
+    pub insurable_loss: u64, // Payment, in `mint`, for work that slashed hosts never delivered
+    pub slashed_flux: u64, // FLUX slashed from those hosts, capping the insurance cover
+    pub mint: Pubkey, // Payment mint, taken from the escrow
+    pub capacity_released: bool, // Every assigned resource got its GPU and VRAM slots back
 }
 
 impl JobAccount {
-    pub const SPACE: usize = 8 + 32 + 32 + 1 + ResourceSpecs::SPACE + 32 + 8 + 8 + 32;
+    pub const SPACE: usize = 8 + 32 + 1 + JobRequirements::SPACE + HostAssignment::SPACE * MAX_HOSTS + 1 + 1 + 32 + 8 + 8 + 8 + 32 + 1 + 32 + 32 + 8 + 8 + 1 + 8 + 8 + 32 + 1;
+
+    /// Seconds after `deadline` during which committed results may still be revealed.
+    pub const REVEAL_WINDOW: i64 = 3600;
+
+    /// Seconds a selected auditor has to confirm the result.
+    pub const AUDIT_WINDOW: i64 = 6 * 3600;
+
+    /// Seconds after the reveal and audit windows during which a finished job is kept open
+    /// for disputes before it can be closed.
+    pub const DISPUTE_WINDOW: i64 = 24 * 3600;
+
+    pub fn assignments(&self) -> &[HostAssignment] {
+        &self.hosts[..self.host_count as usize]
+    }
+
+    /// Earliest time the job's accounts can be closed with `close_job`.
+    pub fn closable_at(&self) -> i64 {
+        (self.deadline + Self::REVEAL_WINDOW).max(self.audit_deadline) + Self::DISPUTE_WINDOW
+    }
+
+    pub fn assignment_index(&self, host: &Pubkey) -> Option<usize> {
+        self.assignments().iter().position(|a| a.host == *host)
+    }
+
+    pub fn commit_count(&self) -> usize {
+        self.assignments()
+            .iter()
+            .filter(|a| a.result_commitment != [0; 32])
+            .count()
+    }
+
+    /// Result hash revealed by at least `quorum` hosts, if any.
+    pub fn quorum_hash(&self) -> Option<[u8; 32]> {
+        let revealed = self.assignments().iter().filter(|a| a.status == AssignmentStatus::Revealed);
+        revealed.clone().map(|a| a.result_hash).find(|hash| {
+            revealed.clone().filter(|a| a.result_hash == *hash).count() >= self.quorum as usize
+        })
+    }
+
+    /// Commitment a host submits before revealing `result_hash` and `salt`.
+    pub fn result_commitment(result_hash: &[u8; 32], salt: &[u8; 32], host: &Pubkey) -> [u8; 32] {
+        hashv(&[result_hash, salt, host.as_ref()]).to_bytes()
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct JobRequirements {
+    pub gpu_count: u8,
+    pub min_vram_gb: u8, // Per GPU
+    pub min_cpu_cores: u8,
+    pub min_compute_rating: u32,
+    pub allowed_gpu_model_count: u8, // Zero allows any model
+    pub allowed_gpu_models: [[u8; MAX_GPU_MODEL_LEN]; MAX_ALLOWED_GPU_MODELS], // Zero-padded names, only the first `allowed_gpu_model_count` are used
+    pub max_price_per_hour: u64, // Per GPU
+    pub duration_hours: u32,
+    pub require_attested: bool,
+}
+
+impl JobRequirements {
+    pub const SPACE: usize = 1 + 1 + 1 + 4 + 1 + MAX_GPU_MODEL_LEN * MAX_ALLOWED_GPU_MODELS + 8 + 4 + 1;
+
+    /// Zero-padded form of `model` as stored in `allowed_gpu_models`, if it fits.
+    pub fn padded_gpu_model(model: &str) -> Option<[u8; MAX_GPU_MODEL_LEN]> {
+        let bytes = model.as_bytes();
+        if bytes.len() > MAX_GPU_MODEL_LEN {
+            return None;
+        }
+        let mut padded = [0; MAX_GPU_MODEL_LEN];
+        padded[..bytes.len()].copy_from_slice(bytes);
+        Some(padded)
+    }
+
+    pub fn allows_gpu_model(&self, model: &str) -> bool {
+        let allowed = &self.allowed_gpu_models[..self.allowed_gpu_model_count as usize];
+        allowed.is_empty() || Self::padded_gpu_model(model).map_or(false, |m| allowed.contains(&m))
+    }
+
+    /// VRAM reserved on a resource for the job.
+    pub fn vram_gb(&self) -> u16 {
+        self.gpu_count as u16 * self.min_vram_gb as u16
+    }
+
+    /// Checks the resource's hardware, preferring attested measurements over self-reported ones.
+    /// Attestations by an attester no longer in the config's active set are ignored.
+    pub fn is_satisfied_by(&self, resource: &ResourceAccount, config: &ConfigAccount) -> bool {
+        let attested = resource.attested && config.is_attester_active(&resource.attestation.attester);
+        let (gpu_model, vram_gb, cpu_cores, compute_rating) = if attested {
+            let a = &resource.attestation;
+            (&a.gpu_model, a.vram_gb, a.cpu_cores, a.compute_rating)
+        } else {
+            let s = &resource.specs;
+            (&s.gpu_model, s.vram_gb, s.cpu_cores, s.compute_rating)
+        };
+
+        (attested || !self.require_attested)
+            && vram_gb >= self.min_vram_gb
+            && cpu_cores >= self.min_cpu_cores
+            && compute_rating >= self.min_compute_rating
+            && self.allows_gpu_model(gpu_model)
+    }
+
+    pub fn accepts_price(&self, price_per_hour: u64) -> bool {
+        price_per_hour <= self.max_price_per_hour
+    }
+
+    /// Payment owed to a host running the job at `price_per_hour` per GPU.
+    pub fn payment_at(&self, price_per_hour: u64) -> Option<u64> {
+        price_per_hour
+            .checked_mul(self.gpu_count as u64)?
+            .checked_mul(self.duration_hours as u64)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+pub struct HostAssignment {
+    pub host: Pubkey,
+    pub resource: Pubkey, // Resource account the host runs the job on
+    pub payment_amount: u64, // Agreed payment at the resource's price
+    pub result_commitment: [u8; 32], // hash(result_hash || salt || host), zero until committed
+    pub result_hash: [u8; 32], // Zero until revealed
+    pub status: AssignmentStatus,
+}
+
+impl HostAssignment {
+    pub const SPACE: usize = 32 + 32 + 8 + 32 + 32 + 1;
+
+    pub const EMPTY: HostAssignment = HostAssignment {
+        host: [0; 32],
+        resource: [0; 32],
+        payment_amount: 0,
+        result_commitment: [0; 32],
+        result_hash: [0; 32],
+        status: AssignmentStatus::Assigned,
+    };
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum JobStatus {
-    Pending,   // Waiting for host assignment
+    Pending,   // Posted for bidding, waiting for host assignment
     Active,    // In progress
     Completed, // Finished successfully
     Failed,    // Failed or disputed
 }
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AuditStatus {
+    Unchecked, // Not sampled yet; `sample_audit` runs once the quorum is reached
+    Skipped,   // Not sampled for audit
+    Pending,   // Waiting for the selected auditor
+    Passed,    // Auditor confirmed the quorum result
+    Failed,    // Auditor reported a different result
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AssignmentStatus {
+    Assigned,  // Running, no commitment yet
+    Committed, // Result commitment posted
+    Revealed,  // Result revealed and verified against the commitment
+    Agreed,    // Matched the quorum result and was paid
+    Disputed,  // Dissented from the quorum or never revealed; open for dispute/slashing
+    Slashed,   // Stake slashed after the job expired without its result
+}
@@ -0,0 +1,47 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Reservation of a whole resource for a client over `[start_at, end_at)`, prepaid in full
+/// and paid out to the host period by period.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct LeaseAccount {
+    pub resource: Pubkey,
+    pub host: Pubkey,
+    pub client: Pubkey, // Lessee, the only client that may start jobs on the resource
+    pub start_at: i64,
+    pub end_at: i64,
+    pub period_seconds: i64,
+    pub rate_per_period: u64,
+    pub deposit: u64, // rate_per_period * total_periods, held by the lease vault
+    pub claimed_periods: u32, // Periods already paid to the host
+    pub client_penalty_bps: u16, // Share of the unused deposit paid to the host if the client terminates
+    pub host_penalty_bps: u16, // Share of the unused deposit slashed from host stake if the host terminates
+    pub status: LeaseStatus,
+}
+
+impl LeaseAccount {
+    pub const SPACE: usize = 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 4 + 2 + 2 + 1;
+
+    pub fn total_periods(&self) -> u32 {
+        ((self.end_at - self.start_at) / self.period_seconds) as u32
+    }
+
+    /// Periods fully elapsed at `now`; these are owed to the host whether or not it ran jobs.
+    pub fn periods_elapsed(&self, now: i64) -> u32 {
+        ((now.clamp(self.start_at, self.end_at) - self.start_at) / self.period_seconds) as u32
+    }
+
+    /// Periods begun at `now`; a period in progress is owed in full on early termination.
+    pub fn periods_started(&self, now: i64) -> u32 {
+        let elapsed = now.clamp(self.start_at, self.end_at) - self.start_at;
+        ((elapsed + self.period_seconds - 1) / self.period_seconds) as u32
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum LeaseStatus {
+    Active,             // Reserved or running
+    Completed,          // Ran to `end_at` and fully paid out
+    TerminatedByClient, // Ended early by the client
+    TerminatedByHost,   // Ended early by the host
+}
@@ -2,8 +2,36 @@ pub mod resource;
 pub mod job;
 pub mod escrow;
 pub mod error;
+pub mod config;
+pub mod auditor;
+pub mod checkpoint;
+pub mod attestation;
+pub mod challenge;
+pub mod auction;
+pub mod order_book;
+pub mod lease;
+pub mod credit;
+pub mod org;
+pub mod fleet;
+pub mod delegation;
+pub mod rewards;
+pub mod price_feed;
 
-pub use resource::{ResourceAccount, ResourceSpecs, ResourceStatus};
-pub use job::{JobAccount, JobStatus};
+pub use resource::{MintPrice, OperatorKey, ResourceAccount, ResourceSpecs, ResourceStatus, MAX_OPERATORS};
+pub use job::{AssignmentStatus, AuditStatus, HostAssignment, JobAccount, JobRequirements, JobStatus, MAX_ALLOWED_GPU_MODELS, MAX_GPU_MODEL_LEN, MAX_HOSTS};
 pub use escrow::{EscrowAccount, EscrowStatus};
-pub use error::FluxError;
\ No newline at end of file
+pub use error::FluxError;
+pub use config::{ConfigAccount, PaymentMint, MAX_ATTESTERS, MAX_PAYMENT_MINTS};
+pub use auditor::{AuditorPool, MAX_AUDITORS};
+pub use checkpoint::{Checkpoint, CheckpointAccount, MAX_CHECKPOINTS};
+pub use attestation::{AttestationStatement, AttestedSpecs, AttesterAccount};
+pub use challenge::{ChallengeAccount, ChallengeStatus, ChallengeVerdict};
+pub use auction::{AuctionAccount, BidAccount};
+pub use order_book::{AskOrder, BidOrder, OrderBook, MAX_ORDERS};
+pub use lease::{LeaseAccount, LeaseStatus};
+pub use credit::CreditAccount;
+pub use org::{OrgAccount, OrgMember, SpendBudget, MAX_ORG_HOSTS};
+pub use fleet::{FleetAccount, MAX_FLEET_RESOURCES};
+pub use delegation::DelegationAccount;
+pub use rewards::{RewardPool, REWARD_SCALE, WORK_RATE_HISTORY};
+pub use price_feed::{PriceFeed, USD_DECIMALS};
\ No newline at end of file
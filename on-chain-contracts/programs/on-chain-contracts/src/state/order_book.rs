@@ -0,0 +1,128 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Maximum number of resting orders on each side of a book.
+pub const MAX_ORDERS: usize = 32;
+
+/// Central limit order book for GPU-hours of one GPU class, with price-time priority.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct OrderBook {
+    pub gpu_class: String, // GPU model traded on this book
+    pub next_order_id: u64,
+    pub ask_count: u8,
+    pub asks: [AskOrder; MAX_ORDERS], // Ascending price, only the first `ask_count` are used
+    pub bid_count: u8,
+    pub bids: [BidOrder; MAX_ORDERS], // Descending price, only the first `bid_count` are used
+}
+
+impl OrderBook {
+    pub const SPACE: usize = (4 + 20) + 8 + 1 + AskOrder::SPACE * MAX_ORDERS + 1 + BidOrder::SPACE * MAX_ORDERS;
+
+    pub fn asks(&self) -> &[AskOrder] {
+        &self.asks[..self.ask_count as usize]
+    }
+
+    pub fn bids(&self) -> &[BidOrder] {
+        &self.bids[..self.bid_count as usize]
+    }
+
+    /// Inserts an ask behind all asks at the same or a better price. Asks cost nothing to post,
+    /// so on a full side the new ask evicts the worst-priced one if it is strictly cheaper.
+    /// Returns false if the side is full and the ask does not beat the worst one.
+    pub fn insert_ask(&mut self, ask: AskOrder) -> bool {
+        let mut count = self.ask_count as usize;
+        if count == MAX_ORDERS {
+            if ask.price_per_hour >= self.asks[count - 1].price_per_hour {
+                return false;
+            }
+            self.remove_ask(count - 1);
+            count -= 1;
+        }
+        let pos = self.asks().iter().position(|a| a.price_per_hour > ask.price_per_hour).unwrap_or(count);
+        self.asks[pos..=count].rotate_right(1);
+        self.asks[pos] = ask;
+        self.ask_count += 1;
+        true
+    }
+
+    /// Inserts a bid behind all bids at the same or a better price. Returns false if the side is full.
+    pub fn insert_bid(&mut self, bid: BidOrder) -> bool {
+        let count = self.bid_count as usize;
+        if count == MAX_ORDERS {
+            return false;
+        }
+        let pos = self.bids().iter().position(|b| b.price_per_hour < bid.price_per_hour).unwrap_or(count);
+        self.bids[pos..=count].rotate_right(1);
+        self.bids[pos] = bid;
+        self.bid_count += 1;
+        true
+    }
+
+    pub fn remove_ask(&mut self, index: usize) {
+        let count = self.ask_count as usize;
+        self.asks[index..count].rotate_left(1);
+        self.asks[count - 1] = AskOrder::EMPTY;
+        self.ask_count -= 1;
+    }
+
+    pub fn remove_bid(&mut self, index: usize) {
+        let count = self.bid_count as usize;
+        self.bids[index..count].rotate_left(1);
+        self.bids[count - 1] = BidOrder::EMPTY;
+        self.bid_count -= 1;
+    }
+
+    /// Highest-priority crossing pair as `(bid_index, ask_index)`. Bids are filled whole, so
+    /// the ask must have at least the bid's hours left.
+    pub fn best_match(&self) -> Option<(usize, usize)> {
+        self.bids().iter().enumerate().find_map(|(b, bid)| {
+            self.asks()
+                .iter()
+                .take_while(|ask| ask.price_per_hour <= bid.price_per_hour)
+                .position(|ask| ask.hours >= bid.hours)
+                .map(|a| (b, a))
+        })
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+pub struct AskOrder {
+    pub order_id: u64,
+    pub host: Pubkey,
+    pub resource: Pubkey, // Resource the GPU-hours run on
+    pub price_per_hour: u64, // Per GPU
+    pub hours: u32, // GPU-hours left to sell
+}
+
+impl AskOrder {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 4;
+
+    pub const EMPTY: AskOrder = AskOrder {
+        order_id: 0,
+        host: [0; 32],
+        resource: [0; 32],
+        price_per_hour: 0,
+        hours: 0,
+    };
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+pub struct BidOrder {
+    pub order_id: u64,
+    pub client: Pubkey,
+    pub job_id: u64, // Job and escrow the fill is created under
+    pub price_per_hour: u64, // Per GPU
+    pub hours: u32, // GPU-hours wanted, filled as a single job
+}
+
+impl BidOrder {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 4;
+
+    pub const EMPTY: BidOrder = BidOrder {
+        order_id: 0,
+        client: [0; 32],
+        job_id: 0,
+        price_per_hour: 0,
+        hours: 0,
+    };
+}
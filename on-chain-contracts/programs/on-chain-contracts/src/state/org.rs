@@ -0,0 +1,70 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Maximum number of hosts an organization can restrict its jobs to.
+pub const MAX_ORG_HOSTS: usize = 16;
+
+/// Team account owning a credit vault that its members launch jobs from.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct OrgAccount {
+    pub admin: Pubkey,
+    pub org_id: u64,
+    pub period_seconds: i64, // Length of a budget period
+    pub budget: SpendBudget, // Org-wide spending per period
+    pub allowed_host_count: u8,
+    pub allowed_hosts: [Pubkey; MAX_ORG_HOSTS], // Only the first `allowed_host_count` are used; none allows any host
+}
+
+impl OrgAccount {
+    pub const SPACE: usize = 32 + 8 + 8 + SpendBudget::SPACE + 1 + 32 * MAX_ORG_HOSTS;
+
+    pub fn allowed_hosts(&self) -> &[Pubkey] {
+        &self.allowed_hosts[..self.allowed_host_count as usize]
+    }
+
+    pub fn allows_host(&self, host: &Pubkey) -> bool {
+        self.allowed_host_count == 0 || self.allowed_hosts().contains(host)
+    }
+}
+
+/// A key allowed to spend from an organization's credit vault.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct OrgMember {
+    pub org: Pubkey,
+    pub member: Pubkey,
+    pub budget: SpendBudget, // This member's spending per period
+    pub active: bool,
+}
+
+impl OrgMember {
+    pub const SPACE: usize = 32 + 32 + SpendBudget::SPACE + 1;
+}
+
+/// Spending limit over fixed-length periods.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Default)]
+pub struct SpendBudget {
+    pub limit: u64, // Zero means unlimited
+    pub period_start: i64,
+    pub spent: u64, // Spent since `period_start`
+}
+
+impl SpendBudget {
+    pub const SPACE: usize = 8 + 8 + 8;
+
+    /// Records `amount` against the current period, starting a new one if the last has ended.
+    /// Returns false, leaving the budget untouched, if it would exceed the limit.
+    pub fn spend(&mut self, amount: u64, now: i64, period_seconds: i64) -> bool {
+        let (period_start, spent) = if now >= self.period_start + period_seconds {
+            (now - (now - self.period_start) % period_seconds, 0)
+        } else {
+            (self.period_start, self.spent)
+        };
+        let spent = match spent.checked_add(amount) {
+            Some(spent) if self.limit == 0 || spent <= self.limit => spent,
+            _ => return false,
+        };
+        self.period_start = period_start;
+        self.spent = spent;
+        true
+    }
+}
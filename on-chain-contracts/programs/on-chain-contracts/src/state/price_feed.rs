@@ -0,0 +1,64 @@
+use pinocchio::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Decimals of USD quotes: prices are in micro-USD.
+pub const USD_DECIMALS: u32 = 6;
+
+/// USD price of one whole token of a payment mint, posted by a trusted publisher. Stands in
+/// for an external oracle account with the same price, confidence and exponent fields.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct PriceFeed {
+    pub mint: Pubkey,
+    pub decimals: u8, // Decimals of `mint`
+    pub publisher: Pubkey, // Key allowed to post prices
+    pub price: u64, // USD price is `price * 10^expo`
+    pub conf: u64, // Confidence interval, in the same units as `price`
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl PriceFeed {
+    pub const SPACE: usize = 32 + 1 + 32 + 8 + 8 + 4 + 8;
+
+    /// Seconds after which a posted price is too stale to convert with.
+    pub const MAX_AGE: i64 = 60;
+
+    /// Widest confidence interval, in basis points of the price, accepted for conversion.
+    pub const MAX_CONF_BPS: u64 = 200;
+
+    pub fn is_stale(&self, now: i64) -> bool {
+        now - self.publish_time > Self::MAX_AGE
+    }
+
+    pub fn is_confident(&self) -> bool {
+        self.price > 0 && self.conf as u128 * 10_000 <= self.price as u128 * Self::MAX_CONF_BPS as u128
+    }
+
+    /// Base units of `mint` worth `usd_micros` micro-USD at the posted price.
+    pub fn usd_to_tokens(&self, usd_micros: u64) -> Option<u64> {
+        // tokens = usd / (price * 10^expo), scaled to base units and from micro-USD.
+        let mut numerator = (usd_micros as u128).checked_mul(10u128.checked_pow(self.decimals as u32)?)?;
+        let mut denominator = (self.price as u128).checked_mul(10u128.pow(USD_DECIMALS))?;
+        if self.expo < 0 {
+            numerator = numerator.checked_mul(10u128.checked_pow(self.expo.unsigned_abs())?)?;
+        } else {
+            denominator = denominator.checked_mul(10u128.checked_pow(self.expo as u32)?)?;
+        }
+        u64::try_from(numerator.checked_div(denominator)?).ok()
+    }
+
+    /// Micro-USD worth of `amount` base units of `mint` at the posted price.
+    pub fn tokens_to_usd(&self, amount: u64) -> Option<u64> {
+        // usd = amount * price * 10^expo, scaled from base units and to micro-USD.
+        let mut numerator = (amount as u128)
+            .checked_mul(self.price as u128)?
+            .checked_mul(10u128.pow(USD_DECIMALS))?;
+        let mut denominator = 10u128.checked_pow(self.decimals as u32)?;
+        if self.expo < 0 {
+            denominator = denominator.checked_mul(10u128.checked_pow(self.expo.unsigned_abs())?)?;
+        } else {
+            numerator = numerator.checked_mul(10u128.checked_pow(self.expo as u32)?)?;
+        }
+        u64::try_from(numerator.checked_div(denominator)?).ok()
+    }
+}
@@ -1,6 +1,12 @@
 use pinocchio::pubkey::Pubkey;
 use borsh::{BorshDeserialize, BorshSerialize};
 
+use super::attestation::AttestedSpecs;
+use super::config::MAX_PAYMENT_MINTS;
+
+/// Maximum number of operator keys a resource can delegate to.
+pub const MAX_OPERATORS: usize = 4;
+
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct ResourceAccount {
     pub host: Pubkey,
@@ -9,10 +15,214 @@ pub struct ResourceAccount {
     pub reputation_score: u16,
     pub staked_flux: u64,
     pub last_updated: i64,
+    pub attested: bool, // Specs confirmed by a registered attester
+    pub attestation: AttestedSpecs, // Snapshot of the attested specs
+    pub allocated_gpus: u8, // GPU slots held by running jobs
+    pub allocated_vram_gb: u16, // VRAM held by running jobs
+    pub lessee: Pubkey, // Client holding the current or upcoming lease, if any
+    pub lease_start: i64,
+    pub lease_end: i64,
+    pub operator_count: u8,
+    pub operators: [OperatorKey; MAX_OPERATORS], // Only the first `operator_count` are used
+    pub fleet: Pubkey, // Fleet whose pooled stake backs the resource, if any
+    pub delegated_flux: u64, // Current value of all delegations, held with the host's stake
+    pub delegation_shares: u64, // Outstanding delegation shares, redeemable pro rata for `delegated_flux`
+    pub delegation_generation: u64, // Bumped when a slash takes all delegated FLUX, voiding older shares
+    pub unbonding_flux: u64, // Undelegated FLUX waiting out the unbonding period, still slashable
+    pub unbonding_shares: u64, // Outstanding unbonding shares, redeemable pro rata for `unbonding_flux`
+    pub unbonding_generation: u64, // Bumped when a slash takes all unbonding FLUX, voiding older unbonding shares
+    pub delegator_share_bps: u16, // Share of the host's job earnings paid to delegators
+    pub pending_delegator_share_bps: u16, // Lower share taking effect at `delegator_share_changes_at`
+    pub delegator_share_changes_at: i64, // Zero when no change is pending
+    pub reward_stake: u64, // Stake the reward pool last weighted the resource by
+    pub reward_delegated_stake: u64, // Delegated part of `reward_stake`, splitting its stake rewards
+    pub reward_debt: u64, // Stake rewards already accounted for at `reward_stake`
+    pub pending_host_rewards: u64,
+    pub pending_delegator_rewards: u64, // Compounded into `delegated_flux` by `claim_rewards` or `compound_rewards`
+    pub work_epoch: u64, // Epoch `work_gpu_hours` were served in
+    pub work_gpu_hours: u64, // GPU-hours not yet converted into rewards
+    pub mint_price_count: u8,
+    pub mint_prices: [MintPrice; MAX_PAYMENT_MINTS], // Prices in non-FLUX mints, only the first `mint_price_count` are used
+    pub usd_price_per_hour: u64, // Micro-USD per GPU-hour, converted at the oracle price; zero prices in tokens
 }
 
 impl ResourceAccount {
-    pub const SPACE: usize = 32 + ResourceSpecs::SPACE + 1 + 2 + 8 + 8;
+    pub const SPACE: usize = 32 + ResourceSpecs::SPACE + 1 + 2 + 8 + 8 + 1 + AttestedSpecs::SPACE + 1 + 2 + 32 + 8 + 8 + 1 + OperatorKey::SPACE * MAX_OPERATORS + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + MintPrice::SPACE * MAX_PAYMENT_MINTS + 8;
+
+    pub fn free_gpus(&self) -> u8 {
+        self.specs.gpu_count - self.allocated_gpus
+    }
+
+    pub fn free_vram_gb(&self) -> u16 {
+        self.specs.total_vram_gb() - self.allocated_vram_gb
+    }
+
+    /// Reserves capacity for a job, returning false if the resource is offline or too full.
+    pub fn allocate(&mut self, gpus: u8, vram_gb: u16) -> bool {
+        if self.status == ResourceStatus::Offline || gpus > self.free_gpus() || vram_gb > self.free_vram_gb() {
+            return false;
+        }
+        self.allocated_gpus += gpus;
+        self.allocated_vram_gb += vram_gb;
+        self.refresh_status();
+        true
+    }
+
+    /// Returns capacity held by a settled or failed job.
+    pub fn release(&mut self, gpus: u8, vram_gb: u16) {
+        self.allocated_gpus = self.allocated_gpus.saturating_sub(gpus);
+        self.allocated_vram_gb = self.allocated_vram_gb.saturating_sub(vram_gb);
+        self.refresh_status();
+    }
+
+    /// Whether `client` may run a job on the resource from `now` until `deadline`. A job that
+    /// overlaps a current or upcoming lease must be the lessee's.
+    pub fn available_to(&self, client: &Pubkey, now: i64, deadline: i64) -> bool {
+        self.lessee == *client || deadline <= self.lease_start || now >= self.lease_end
+    }
+
+    /// Host stake plus delegated stake.
+    pub fn effective_stake(&self) -> u64 {
+        self.staked_flux + self.delegated_flux
+    }
+
+    /// Deducts a slash from the host's stake, the delegators' stake and the FLUX they are
+    /// unbonding, in proportion to each. `amount` must not exceed their sum.
+    pub fn slash(&mut self, amount: u64) {
+        let total = (self.effective_stake() + self.unbonding_flux).max(1) as u128;
+        let unbonding_part = (amount as u128 * self.unbonding_flux as u128 / total) as u64;
+        let rest = amount - unbonding_part;
+        let host_part = (rest as u128 * self.staked_flux as u128 / self.effective_stake().max(1) as u128) as u64;
+        self.unbonding_flux -= unbonding_part;
+        self.staked_flux -= host_part;
+        self.delegated_flux -= rest - host_part;
+        self.void_wiped_delegations();
+    }
+
+    /// Once a slash has taken all delegated or unbonding FLUX the outstanding shares of that pool
+    /// are worthless. They are voided by starting a new generation, so that they do not dilute
+    /// later delegators.
+    pub fn void_wiped_delegations(&mut self) {
+        if self.unbonding_flux == 0 && self.unbonding_shares > 0 {
+            self.unbonding_shares = 0;
+            self.unbonding_generation += 1;
+        }
+        if self.delegated_flux == 0 && self.delegation_shares > 0 {
+            self.delegation_shares = 0;
+            self.delegation_generation += 1;
+        }
+    }
+
+    /// Shares minted for delegating `amount`, at the current share value. The first
+    /// delegation of a generation mints shares 1:1.
+    pub fn shares_for(&self, amount: u64) -> u64 {
+        if self.delegation_shares == 0 {
+            amount
+        } else {
+            // `void_wiped_delegations` keeps `delegated_flux` non-zero while shares are outstanding.
+            (amount as u128 * self.delegation_shares as u128 / self.delegated_flux.max(1) as u128) as u64
+        }
+    }
+
+    /// FLUX redeemable for `shares` delegation shares.
+    pub fn value_of_shares(&self, shares: u64) -> u64 {
+        (shares as u128 * self.delegated_flux as u128 / self.delegation_shares.max(1) as u128) as u64
+    }
+
+    /// Unbonding shares minted for starting to unbond `amount`. The first unbonding of a
+    /// generation mints shares 1:1.
+    pub fn unbonding_shares_for(&self, amount: u64) -> u64 {
+        if self.unbonding_shares == 0 {
+            amount
+        } else {
+            // `void_wiped_delegations` keeps `unbonding_flux` non-zero while shares are outstanding.
+            (amount as u128 * self.unbonding_shares as u128 / self.unbonding_flux as u128) as u64
+        }
+    }
+
+    /// FLUX redeemable for `shares` unbonding shares, net of slashes since unbonding began.
+    pub fn value_of_unbonding_shares(&self, shares: u64) -> u64 {
+        (shares as u128 * self.unbonding_flux as u128 / self.unbonding_shares.max(1) as u128) as u64
+    }
+
+    /// Delegator share of earnings in force at `now`, taking a pending cut into account.
+    pub fn delegator_share_bps_at(&self, now: i64) -> u16 {
+        if self.delegator_share_changes_at != 0 && now >= self.delegator_share_changes_at {
+            self.pending_delegator_share_bps
+        } else {
+            self.delegator_share_bps
+        }
+    }
+
+    pub fn mint_prices(&self) -> &[MintPrice] {
+        &self.mint_prices[..self.mint_price_count as usize]
+    }
+
+    /// Price per GPU-hour in `mint`: `specs.price_per_hour` for FLUX, otherwise the host's quote
+    /// for that mint, if any.
+    pub fn price_in(&self, mint: &Pubkey, flux_mint: &Pubkey) -> Option<u64> {
+        if mint == flux_mint {
+            return Some(self.specs.price_per_hour);
+        }
+        self.mint_prices().iter().find(|p| p.mint == *mint).map(|p| p.price_per_hour)
+    }
+
+    pub fn operators(&self) -> &[OperatorKey] {
+        &self.operators[..self.operator_count as usize]
+    }
+
+    /// Whether `key` is an unexpired operator key of this resource.
+    pub fn is_operator(&self, key: &Pubkey, now: i64) -> bool {
+        self.operators().iter().any(|o| o.key == *key && now < o.expires_at)
+    }
+
+    /// Whether `key` may run the resource: the host itself or one of its operators.
+    pub fn can_operate(&self, key: &Pubkey, now: i64) -> bool {
+        self.host == *key || self.is_operator(key, now)
+    }
+
+    /// Derives the status from utilization; `Offline` is only changed by the host.
+    pub fn refresh_status(&mut self) {
+        self.status = match self.status {
+            ResourceStatus::Offline => ResourceStatus::Offline,
+            _ if self.allocated_gpus == 0 => ResourceStatus::Idle,
+            _ if self.free_gpus() == 0 => ResourceStatus::Busy,
+            _ => ResourceStatus::Partial,
+        };
+    }
+}
+
+/// Worker key allowed to submit results, heartbeats and status changes for a resource,
+/// but never to move its stake or earnings.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+pub struct OperatorKey {
+    pub key: Pubkey,
+    pub expires_at: i64,
+}
+
+impl OperatorKey {
+    pub const SPACE: usize = 32 + 8;
+
+    pub const EMPTY: OperatorKey = OperatorKey {
+        key: [0; 32],
+        expires_at: 0,
+    };
+}
+
+/// A resource's price per GPU-hour in a payment mint other than FLUX.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy)]
+pub struct MintPrice {
+    pub mint: Pubkey,
+    pub price_per_hour: u64,
+}
+
+impl MintPrice {
+    pub const SPACE: usize = 32 + 8;
+
+    pub const EMPTY: MintPrice = MintPrice {
+        mint: [0; 32],
+        price_per_hour: 0,
+    };
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
@@ -22,11 +232,17 @@ pub struct ResourceSpecs {
     pub vram_gb: u8,
     pub cpu_cores: u8,
     pub compute_rating: u32,
-    pub price_per_hour: u64,
+    pub price_per_hour: u64, // Per GPU
+    pub gpu_count: u8, // GPUs on the resource, or GPUs requested when copied into a job
 }
 
 impl ResourceSpecs {
-    pub const SPACE: usize = 8 + 4 + 20 + 1 + 1 + 4 + 8;
+    pub const SPACE: usize = 8 + 4 + 20 + 1 + 1 + 4 + 8 + 1;
+
+    /// VRAM across all GPUs, with `vram_gb` per GPU.
+    pub fn total_vram_gb(&self) -> u16 {
+        self.gpu_count as u16 * self.vram_gb as u16
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
@@ -34,4 +250,5 @@ pub enum ResourceStatus {
     Idle,
     Busy,
     Offline,
+    Partial, // Some GPU slots allocated, still accepting jobs
 }
@@ -0,0 +1,104 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::resource::ResourceAccount;
+
+/// Number of past epochs whose work reward rate is kept; older unclaimed work is forfeited.
+pub const WORK_RATE_HISTORY: usize = 16;
+
+/// Fixed-point scale of the reward accumulators.
+pub const REWARD_SCALE: u128 = 1_000_000_000_000;
+
+/// Emissions pool paying FLUX to resources each epoch, split between stake and completed work.
+/// The FLUX to be emitted is held in the `[b"rewards_vault"]` token account, owned by the pool PDA.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct RewardPool {
+    pub epoch: u64, // Current epoch, starting at 0
+    pub epoch_started_at: i64,
+    pub epoch_length: i64, // Seconds
+    pub emission_per_epoch: u64,
+    pub work_share_bps: u16, // Share of each epoch's emission paid for GPU-hours served, the rest for stake
+    pub acc_reward_per_stake: u128, // Stake rewards per unit of stake since genesis, scaled by `REWARD_SCALE`
+    pub total_stake: u64, // Sum of every resource's `reward_stake`
+    pub epoch_gpu_hours: u64, // GPU-hours settled in the current epoch
+    pub work_rates: [u128; WORK_RATE_HISTORY], // Reward per GPU-hour of recent epochs, indexed by `epoch % WORK_RATE_HISTORY`
+}
+
+impl RewardPool {
+    pub const SPACE: usize = 8 + 8 + 8 + 8 + 2 + 16 + 8 + 8 + 16 * WORK_RATE_HISTORY;
+
+    /// Reward per GPU-hour served in a finished `epoch`, if it is still in the history.
+    pub fn work_rate(&self, epoch: u64) -> Option<u128> {
+        if epoch >= self.epoch || self.epoch - epoch > WORK_RATE_HISTORY as u64 {
+            return None;
+        }
+        Some(self.work_rates[epoch as usize % WORK_RATE_HISTORY])
+    }
+
+    /// Distributes the current epoch's emission and starts the next one.
+    pub fn advance(&mut self) {
+        let work_emission = (self.emission_per_epoch as u128 * self.work_share_bps as u128 / 10_000) as u64;
+        let stake_emission = self.emission_per_epoch - work_emission;
+
+        if self.total_stake > 0 {
+            self.acc_reward_per_stake += stake_emission as u128 * REWARD_SCALE / self.total_stake as u128;
+        }
+        self.work_rates[self.epoch as usize % WORK_RATE_HISTORY] = if self.epoch_gpu_hours > 0 {
+            work_emission as u128 * REWARD_SCALE / self.epoch_gpu_hours as u128
+        } else {
+            0
+        };
+
+        self.epoch += 1;
+        self.epoch_started_at += self.epoch_length;
+        self.epoch_gpu_hours = 0;
+    }
+
+    /// Moves everything the resource has earned since it was last touched into its pending rewards,
+    /// split between host and delegators. Stake rewards are split by the stake the resource was
+    /// weighted by, work rewards at the delegator share in force at `now`.
+    pub fn accrue(&self, resource: &mut ResourceAccount, now: i64) {
+        let accumulated = (resource.reward_stake as u128 * self.acc_reward_per_stake / REWARD_SCALE) as u64;
+        let stake_rewards = accumulated - resource.reward_debt;
+        resource.reward_debt = accumulated;
+        let delegator_stake_rewards =
+            (stake_rewards as u128 * resource.reward_delegated_stake as u128 / resource.reward_stake.max(1) as u128) as u64;
+        resource.pending_delegator_rewards += delegator_stake_rewards;
+        resource.pending_host_rewards += stake_rewards - delegator_stake_rewards;
+
+        if resource.work_gpu_hours > 0 && resource.work_epoch < self.epoch {
+            let rate = self.work_rate(resource.work_epoch).unwrap_or(0);
+            let work_rewards = (resource.work_gpu_hours as u128 * rate / REWARD_SCALE) as u64;
+            let delegator_work_rewards = if resource.delegated_flux > 0 {
+                (work_rewards as u128 * resource.delegator_share_bps_at(now) as u128 / 10_000) as u64
+            } else {
+                0
+            };
+            resource.pending_delegator_rewards += delegator_work_rewards;
+            resource.pending_host_rewards += work_rewards - delegator_work_rewards;
+            resource.work_gpu_hours = 0;
+        }
+    }
+
+    /// Accrues, then re-weights the resource by its current stake. Fleet members are backed by
+    /// the fleet's pooled stake and earn work rewards only.
+    pub fn sync_stake(&mut self, resource: &mut ResourceAccount, now: i64) {
+        self.accrue(resource, now);
+        let (stake, delegated) = if resource.fleet == [0; 32] {
+            (resource.effective_stake(), resource.delegated_flux)
+        } else {
+            (0, 0)
+        };
+        self.total_stake = self.total_stake - resource.reward_stake + stake;
+        resource.reward_stake = stake;
+        resource.reward_delegated_stake = delegated;
+        resource.reward_debt = (stake as u128 * self.acc_reward_per_stake / REWARD_SCALE) as u64;
+    }
+
+    /// Credits GPU-hours served by the resource to the current epoch.
+    pub fn record_work(&mut self, resource: &mut ResourceAccount, gpu_hours: u64, now: i64) {
+        self.accrue(resource, now);
+        resource.work_epoch = self.epoch;
+        resource.work_gpu_hours += gpu_hours;
+        self.epoch_gpu_hours += gpu_hours;
+    }
+}
@@ -0,0 +1,1264 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, web3 } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { createHash } from "crypto";
+
+// The program dispatches on a leading instruction byte and has no IDL, so instructions
+// are encoded by hand here, field by field as the handlers read them.
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+export const connection = provider.connection;
+export const wallet = provider.wallet.publicKey;
+
+export const PROGRAM_ID = new web3.PublicKey(
+  "C9xzMFbaR39ftisYXsnbELsPpxgsMeeLW5fVH4fSVNiR"
+);
+export const TOKEN_PROGRAM_ID = new web3.PublicKey(
+  "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+);
+export const TOKEN_2022_PROGRAM_ID = new web3.PublicKey(
+  "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+);
+export const SYSTEM_PROGRAM_ID = web3.SystemProgram.programId;
+export const BPF_LOADER_UPGRADEABLE_ID = new web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+// Config the protocol is initialized with.
+export const PROTOCOL_FEE_BPS = 500;
+export const INSURANCE_FEE_BPS = 2_000;
+export const INSURANCE_SLASH_BPS = 2_000;
+export const INSURANCE_COVER_BPS = 5_000;
+export const INSURANCE_MAX_PAYOUT = 1_000_000_000;
+
+// Account sizes, matching the `SPACE` constants of the program's state.
+export const JOB_ACCOUNT_SPACE = 1048;
+export const ESCROW_ACCOUNT_SPACE = 145;
+
+// SPL token layouts. Token-2022 sizes include the transfer fee config (mint) and
+// transfer fee amount (account) extensions.
+const MINT_SIZE = 82;
+const ACCOUNT_SIZE = 165;
+const FEE_MINT_SIZE = 278;
+const FEE_ACCOUNT_SIZE = 178;
+
+export const u8 = (n: number) => Buffer.from([n]);
+
+export const bool = (b: boolean) => u8(b ? 1 : 0);
+
+export const u16 = (n: number) => {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(n);
+  return buf;
+};
+
+export const u32 = (n: number) => {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(n);
+  return buf;
+};
+
+export const i32 = (n: number) => {
+  const buf = Buffer.alloc(4);
+  buf.writeInt32LE(n);
+  return buf;
+};
+
+export const u64 = (n: number | BN) => new BN(n).toArrayLike(Buffer, "le", 8);
+export const i64 = (n: number) =>
+  new BN(n).toTwos(64).toArrayLike(Buffer, "le", 8);
+
+export const str = (s: string) => {
+  const bytes = Buffer.from(s, "utf8");
+  return Buffer.concat([u32(bytes.length), bytes]);
+};
+
+export const pda = (...seeds: (string | Buffer | web3.PublicKey)[]) =>
+  web3.PublicKey.findProgramAddressSync(
+    seeds.map((s) =>
+      typeof s === "string"
+        ? Buffer.from(s)
+        : s instanceof web3.PublicKey
+        ? s.toBuffer()
+        : s
+    ),
+    PROGRAM_ID
+  )[0];
+
+export const configPda = pda("config");
+export const programDataPda = web3.PublicKey.findProgramAddressSync(
+  [PROGRAM_ID.toBuffer()],
+  BPF_LOADER_UPGRADEABLE_ID
+)[0];
+export const auditorPoolPda = pda("auditors");
+export const insurancePda = pda("insurance");
+export const rewardPoolPda = pda("rewards");
+export const resourcePda = (host: web3.PublicKey, id: number) =>
+  pda("resource", host, u64(id));
+export const stakePda = (owner: web3.PublicKey) => pda("stake", owner);
+export const escrowPda = (client: web3.PublicKey, jobId: number) =>
+  pda("escrow", client, u64(jobId));
+export const escrowVaultPda = (escrow: web3.PublicKey) =>
+  pda("escrow_vault", escrow);
+export const jobPda = (client: web3.PublicKey, jobId: number) =>
+  pda("job", client, u64(jobId));
+export const checkpointPda = (job: web3.PublicKey, host: web3.PublicKey) =>
+  pda("checkpoints", job, host);
+export const priceFeedPda = (mint: web3.PublicKey) => pda("price_feed", mint);
+export const attesterPda = (key: web3.PublicKey) => pda("attester", key);
+export const challengePda = (resource: web3.PublicKey) =>
+  pda("challenge", resource);
+
+/** Writable account, optionally signing. */
+export const w = (pubkey: web3.PublicKey, isSigner = false) => ({
+  pubkey,
+  isSigner,
+  isWritable: true,
+});
+
+/** Read-only account. */
+export const r = (pubkey: web3.PublicKey) => ({
+  pubkey,
+  isSigner: false,
+  isWritable: false,
+});
+
+export const flatten = <T>(groups: T[][]) => ([] as T[]).concat(...groups);
+
+/** Instruction of this program: the instruction byte followed by its data. */
+export const ix = (
+  instruction: number,
+  keys: web3.AccountMeta[],
+  ...data: Buffer[]
+) =>
+  new web3.TransactionInstruction({
+    programId: PROGRAM_ID,
+    keys,
+    data: Buffer.concat([u8(instruction), ...data]),
+  });
+
+export async function send(
+  instructions: web3.TransactionInstruction[],
+  signers: web3.Keypair[] = []
+) {
+  const tx = new web3.Transaction().add(
+    web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+    ...instructions
+  );
+  return provider.sendAndConfirm(tx, signers);
+}
+
+/** Expects the transaction to fail with `ProgramError::Custom(code)`. */
+export async function expectError(tx: Promise<unknown>, code: number) {
+  try {
+    await tx;
+  } catch (err) {
+    const logs: string[] = (err as any).logs ?? [];
+    const text = [String(err), ...logs].join("\n");
+    expect(text).to.match(
+      new RegExp(`custom program error: 0x${code.toString(16)}\\b`)
+    );
+    return;
+  }
+  expect.fail(`expected custom program error ${code}`);
+}
+
+export async function fundedKeypair() {
+  const keypair = web3.Keypair.generate();
+  await send([
+    web3.SystemProgram.transfer({
+      fromPubkey: wallet,
+      toPubkey: keypair.publicKey,
+      lamports: 2 * web3.LAMPORTS_PER_SOL,
+    }),
+  ]);
+  return keypair;
+}
+
+/** Unix timestamp of the cluster's Clock sysvar. */
+export async function chainTime() {
+  const clock = await connection.getAccountInfo(web3.SYSVAR_CLOCK_PUBKEY);
+  return new BN(clock.data.subarray(32, 40), "le").toNumber();
+}
+
+/** Waits until the cluster clock is past `timestamp`. */
+export async function waitPast(timestamp: number) {
+  while ((await chainTime()) <= timestamp) {
+    await new Promise((resolve) => setTimeout(resolve, 400));
+  }
+}
+
+export interface Mint {
+  address: web3.PublicKey;
+  tokenProgram: web3.PublicKey;
+  accountSize: number;
+}
+
+async function createAccountIx(
+  account: web3.PublicKey,
+  space: number,
+  owner: web3.PublicKey
+) {
+  return web3.SystemProgram.createAccount({
+    fromPubkey: wallet,
+    newAccountPubkey: account,
+    lamports: await connection.getMinimumBalanceForRentExemption(space),
+    space,
+    programId: owner,
+  });
+}
+
+const tokenIx = (
+  tokenProgram: web3.PublicKey,
+  keys: web3.AccountMeta[],
+  ...data: Buffer[]
+) =>
+  new web3.TransactionInstruction({
+    programId: tokenProgram,
+    keys,
+    data: Buffer.concat(data),
+  });
+
+/**
+ * Creates a mint with the wallet as mint authority: a classic SPL mint, or a Token-2022
+ * mint charging `transferFeeBps` on every transfer.
+ */
+export async function createMint(
+  decimals: number,
+  transferFeeBps?: number
+): Promise<Mint> {
+  const mint = web3.Keypair.generate();
+  const withFee = transferFeeBps !== undefined;
+  const tokenProgram = withFee ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+
+  const instructions = [
+    await createAccountIx(
+      mint.publicKey,
+      withFee ? FEE_MINT_SIZE : MINT_SIZE,
+      tokenProgram
+    ),
+  ];
+  if (withFee) {
+    // InitializeTransferFeeConfig without fee authorities.
+    instructions.push(
+      tokenIx(
+        tokenProgram,
+        [w(mint.publicKey)],
+        u8(26),
+        u8(0),
+        u8(0),
+        u8(0),
+        u16(transferFeeBps),
+        u64(1_000_000_000)
+      )
+    );
+  }
+  // InitializeMint2 without a freeze authority.
+  instructions.push(
+    tokenIx(
+      tokenProgram,
+      [w(mint.publicKey)],
+      u8(20),
+      u8(decimals),
+      wallet.toBuffer(),
+      u8(0)
+    )
+  );
+  await send(instructions, [mint]);
+
+  return {
+    address: mint.publicKey,
+    tokenProgram,
+    accountSize: withFee ? FEE_ACCOUNT_SIZE : ACCOUNT_SIZE,
+  };
+}
+
+/** Creates a token account of `mint` owned by `owner` and mints `amount` into it. */
+export async function createTokenAccount(
+  mint: Mint,
+  owner: web3.PublicKey,
+  amount = 0
+) {
+  const account = web3.Keypair.generate();
+  const instructions = [
+    await createAccountIx(account.publicKey, mint.accountSize, mint.tokenProgram),
+    // InitializeAccount3
+    tokenIx(
+      mint.tokenProgram,
+      [w(account.publicKey), r(mint.address)],
+      u8(18),
+      owner.toBuffer()
+    ),
+  ];
+  if (amount > 0) {
+    instructions.push(mintToIx(mint, account.publicKey, amount));
+  }
+  await send(instructions, [account]);
+  return account.publicKey;
+}
+
+export const mintToIx = (
+  mint: Mint,
+  account: web3.PublicKey,
+  amount: number
+) =>
+  tokenIx(
+    mint.tokenProgram,
+    [w(mint.address), w(account), w(wallet, true)],
+    u8(7),
+    u64(amount)
+  );
+
+/** Little-endian u64 field at `offset` in an account's data. */
+export async function readU64(account: web3.PublicKey, offset: number) {
+  const info = await connection.getAccountInfo(account);
+  return new BN(info.data.subarray(offset, offset + 8), "le").toNumber();
+}
+
+/** Token balance of an SPL or Token-2022 account. */
+export const balance = (account: web3.PublicKey) => readU64(account, 64);
+
+export interface Protocol {
+  flux: Mint;
+  treasury: web3.PublicKey;
+  usdc: Mint;
+  usdcTreasury: web3.PublicKey;
+  feeToken: Mint; // Token-2022 mint charging a 1% transfer fee
+  feeTokenTreasury: web3.PublicKey;
+}
+
+let protocol: Promise<Protocol> | undefined;
+
+/**
+ * Initializes the config and the insurance pool once per validator, with FLUX, a second
+ * payment mint and a Token-2022 mint with a transfer fee accepted. The wallet is the admin.
+ */
+export function setupProtocol() {
+  if (!protocol) {
+    protocol = initializeProtocol();
+  }
+  return protocol;
+}
+
+async function initializeProtocol(): Promise<Protocol> {
+  const flux = await createMint(6);
+  const usdc = await createMint(6);
+  const feeToken = await createMint(6, 100);
+  const p: Protocol = {
+    flux,
+    treasury: await createTokenAccount(flux, wallet),
+    usdc,
+    usdcTreasury: await createTokenAccount(usdc, wallet),
+    feeToken,
+    feeTokenTreasury: await createTokenAccount(feeToken, wallet),
+  };
+
+  await send([
+    ix(
+      12, // initialize_config
+      [
+        w(wallet, true),
+        w(configPda),
+        w(auditorPoolPda),
+        r(SYSTEM_PROGRAM_ID),
+        r(programDataPda),
+      ],
+      configData(p)
+    ),
+    ix(
+      66, // initialize_insurance
+      [
+        w(wallet, true),
+        r(configPda),
+        w(insurancePda),
+        r(TOKEN_PROGRAM_ID),
+        r(SYSTEM_PROGRAM_ID),
+        r(flux.address),
+      ]
+    ),
+  ]);
+  return p;
+}
+
+/** Config data as `parse_config` reads it. */
+export const configData = (p: Protocol, auditRateBps = 0) =>
+  Buffer.concat([
+    p.treasury.toBuffer(),
+    u16(PROTOCOL_FEE_BPS),
+    u16(auditRateBps),
+    u64(0), // min_auditor_stake
+    u16(INSURANCE_FEE_BPS),
+    u16(INSURANCE_SLASH_BPS),
+    u16(INSURANCE_COVER_BPS),
+    u64(INSURANCE_MAX_PAYOUT),
+    p.flux.address.toBuffer(),
+    u8(2),
+    p.usdc.address.toBuffer(),
+    p.usdcTreasury.toBuffer(),
+    p.feeToken.address.toBuffer(),
+    p.feeTokenTreasury.toBuffer(),
+  ]);
+
+export interface ResourceOptions {
+  id?: number;
+  gpuModel?: string;
+  vramGb?: number;
+  pricePerHour?: number;
+  gpuCount?: number;
+}
+
+/** `ResourceSpecs` as `register_resource` reads them. */
+export const specsData = (opts: ResourceOptions = {}) =>
+  Buffer.concat([
+    u64(opts.id ?? 1),
+    str(opts.gpuModel ?? "A100"),
+    u8(opts.vramGb ?? 80),
+    u8(32), // cpu_cores
+    u32(1_000), // compute_rating
+    u64(opts.pricePerHour ?? 1_000),
+    u8(opts.gpuCount ?? 1),
+  ]);
+
+export async function registerResource(
+  host: web3.Keypair,
+  opts: ResourceOptions = {}
+) {
+  const resource = resourcePda(host.publicKey, opts.id ?? 1);
+  await send(
+    [
+      ix(
+        0, // register_resource
+        [w(host.publicKey, true), w(resource), r(SYSTEM_PROGRAM_ID)],
+        specsData(opts)
+      ),
+    ],
+    [host]
+  );
+  return resource;
+}
+
+export async function stakeFlux(
+  p: Protocol,
+  host: web3.Keypair,
+  resource: web3.PublicKey,
+  tokenAccount: web3.PublicKey,
+  amount: number
+) {
+  await send(
+    [
+      ix(
+        7, // stake_flux
+        [
+          w(host.publicKey, true),
+          w(resource),
+          w(tokenAccount),
+          r(TOKEN_PROGRAM_ID),
+          r(p.flux.address),
+          w(stakePda(resource)),
+          w(rewardPoolPda),
+          r(SYSTEM_PROGRAM_ID),
+          r(configPda),
+        ],
+        u64(amount)
+      ),
+    ],
+    [host]
+  );
+}
+
+export const unstakeFlux = (
+  p: Protocol,
+  host: web3.Keypair,
+  resource: web3.PublicKey,
+  tokenAccount: web3.PublicKey,
+  amount: number
+) =>
+  send(
+    [
+      ix(
+        8, // unstake_flux
+        [
+          w(host.publicKey, true),
+          w(resource),
+          w(tokenAccount),
+          r(TOKEN_PROGRAM_ID),
+          r(p.flux.address),
+          w(stakePda(resource)),
+          w(rewardPoolPda),
+        ],
+        u64(amount)
+      ),
+    ],
+    [host]
+  );
+
+// `JobRequirements` bounds on the GPU model allow-list.
+const MAX_ALLOWED_GPU_MODELS = 4;
+const MAX_GPU_MODEL_LEN = 20;
+
+export interface Requirements {
+  gpuCount?: number;
+  minVramGb?: number;
+  minComputeRating?: number;
+  allowedGpuModels?: string[];
+  durationHours?: number;
+  maxPricePerHour?: number;
+  requireAttested?: boolean;
+}
+
+export const requirementsData = (req: Requirements = {}) =>
+  Buffer.concat([
+    u8(req.gpuCount ?? 1),
+    u8(req.minVramGb ?? 0),
+    u8(0), // min_cpu_cores
+    u32(req.minComputeRating ?? 0),
+    u8((req.allowedGpuModels ?? []).length),
+    ...Array.from({ length: MAX_ALLOWED_GPU_MODELS }, (_, i) => {
+      const model = Buffer.alloc(MAX_GPU_MODEL_LEN);
+      model.write((req.allowedGpuModels ?? [])[i] ?? "");
+      return model;
+    }),
+    u64(req.maxPricePerHour ?? 1_000_000_000),
+    u32(req.durationHours ?? 1),
+    bool(req.requireAttested ?? false),
+  ]);
+
+export interface Job {
+  client: web3.Keypair;
+  id: number;
+  address: web3.PublicKey;
+  escrow: web3.PublicKey;
+  vault: web3.PublicKey;
+  mint: Mint;
+}
+
+export function jobAccounts(client: web3.Keypair, id: number, mint: Mint): Job {
+  const escrow = escrowPda(client.publicKey, id);
+  return {
+    client,
+    id,
+    address: jobPda(client.publicKey, id),
+    escrow,
+    vault: escrowVaultPda(escrow),
+    mint,
+  };
+}
+
+export async function depositEscrow(
+  job: Job,
+  tokenAccount: web3.PublicKey,
+  amount: number
+) {
+  await send(
+    [
+      ix(
+        5, // deposit_escrow
+        [
+          w(job.client.publicKey, true),
+          w(job.escrow),
+          w(job.vault),
+          w(tokenAccount),
+          r(job.mint.tokenProgram),
+          r(configPda),
+          r(job.mint.address),
+          r(SYSTEM_PROGRAM_ID),
+        ],
+        u64(amount),
+        u64(job.id)
+      ),
+    ],
+    [job.client]
+  );
+}
+
+export interface JobOptions {
+  id?: number;
+  mint?: Mint;
+  deadline: number;
+  quorum?: number;
+  requirements?: Requirements;
+}
+
+// How `start_job` funds the job's escrow.
+export const FUNDING_ESCROW = 0;
+export const FUNDING_CREDIT = 1;
+export const FUNDING_ORG = 2;
+
+/**
+ * Starts the job on `resources`, paying each host its resource's price in the escrow's
+ * mint. Every resource after the first runs the job redundantly. Credit and organization
+ * funding pass their accounts in `fundingAccounts`, between the price feed and the
+ * additional resources.
+ */
+export async function startJob(
+  client: web3.Keypair,
+  job: Job,
+  resources: web3.PublicKey[],
+  opts: JobOptions,
+  funding = FUNDING_ESCROW,
+  fundingAccounts: web3.AccountMeta[] = []
+) {
+  await send(
+    [
+      ix(
+        2, // start_job
+        [
+          w(client.publicKey, true),
+          w(job.address),
+          w(resources[0]),
+          w(job.escrow),
+          r(SYSTEM_PROGRAM_ID),
+          r(configPda),
+          r(priceFeedPda(job.mint.address)),
+          ...fundingAccounts,
+          ...resources.slice(1).map((resource) => w(resource)),
+        ],
+        u64(job.id),
+        i64(opts.deadline),
+        u8(opts.quorum ?? 1),
+        u8(funding),
+        requirementsData(opts.requirements)
+      ),
+    ],
+    [client]
+  );
+}
+
+/** Deposits `amount` into a new escrow and starts the job on `resources` with it. */
+export async function openJob(
+  p: Protocol,
+  client: web3.Keypair,
+  tokenAccount: web3.PublicKey,
+  amount: number,
+  resources: web3.PublicKey[],
+  opts: JobOptions
+) {
+  const job = jobAccounts(client, opts.id ?? 1, opts.mint ?? p.flux);
+  await depositEscrow(job, tokenAccount, amount);
+  await startJob(client, job, resources, opts);
+  return job;
+}
+
+/** A host assigned to a job, with its token account in the job's mint. */
+export interface Assignment {
+  host: web3.PublicKey;
+  resource: web3.PublicKey;
+  tokenAccount: web3.PublicKey;
+  staking?: web3.PublicKey; // Defaults to the resource's own staking account
+}
+
+/**
+ * Expires a job past its deadline, refunding `clientToken` and paying slashes in FLUX to
+ * `clientFlux`. A job funded by an organization refunds into the organization's credit
+ * vault, passed as both token accounts, with its credit account `orgCredit`.
+ */
+export async function expireJob(
+  p: Protocol,
+  job: Job,
+  clientToken: web3.PublicKey,
+  clientFlux: web3.PublicKey,
+  assignments: Assignment[],
+  orgCredit?: web3.PublicKey
+) {
+  await send([
+    ix(
+      9, // expire_job
+      [
+        w(wallet, true),
+        w(job.address),
+        w(job.escrow),
+        w(job.vault),
+        w(clientToken),
+        r(job.mint.tokenProgram),
+        r(configPda),
+        w(insurancePda),
+        w(rewardPoolPda),
+        w(clientFlux),
+        r(p.flux.address),
+        r(job.mint.address),
+        ...flatten(
+          assignments.map((a) => [
+            w(a.resource),
+            w(a.staking ?? stakePda(a.resource)),
+            r(checkpointPda(job.address, a.host)),
+            w(a.tokenAccount),
+          ])
+        ),
+        ...(orgCredit ? [w(orgCredit)] : []),
+      ]
+    ),
+  ]);
+}
+
+/**
+ * Settles a job whose results reached quorum. The protocol fee goes to `treasury`, or to
+ * `auditorToken` if the job passed its audit.
+ */
+export async function settleJob(
+  job: Job,
+  clientToken: web3.PublicKey,
+  treasury: web3.PublicKey,
+  assignments: Assignment[],
+  auditorToken = treasury
+) {
+  await send([
+    ix(
+      11, // settle_job
+      [
+        w(wallet, true),
+        w(job.address),
+        w(job.escrow),
+        w(job.vault),
+        w(clientToken),
+        r(job.mint.tokenProgram),
+        r(configPda),
+        w(treasury),
+        w(auditorToken),
+        w(rewardPoolPda),
+        w(insurancePda),
+        r(job.mint.address),
+        ...flatten(
+          assignments.map((a) => [
+            w(a.resource),
+            w(a.tokenAccount),
+            w(a.staking ?? stakePda(a.resource)),
+          ])
+        ),
+      ]
+    ),
+  ]);
+}
+
+/** `hash(result_hash || salt || host)`, as `JobAccount::result_commitment` computes it. */
+export const resultCommitment = (
+  resultHash: Buffer,
+  salt: Buffer,
+  host: web3.PublicKey
+) =>
+  createHash("sha256")
+    .update(Buffer.concat([resultHash, salt, host.toBuffer()]))
+    .digest();
+
+/** An operator key acting for its host's `resource`. */
+export interface Operator {
+  signer: web3.Keypair;
+  resource: web3.PublicKey;
+}
+
+/** Commits `host`'s result, signed by `operator` instead of the host when given. */
+export const commitResult = (
+  host: web3.Keypair,
+  job: Job,
+  resultHash: Buffer,
+  salt: Buffer,
+  operator?: Operator
+) =>
+  send(
+    [
+      ix(
+        3, // submit_job_result
+        [
+          w((operator?.signer ?? host).publicKey, true),
+          w(job.address),
+          ...(operator ? [r(operator.resource)] : []),
+        ],
+        resultCommitment(resultHash, salt, host.publicKey)
+      ),
+    ],
+    [operator?.signer ?? host]
+  );
+
+/** Reveals `host`'s result, signed by `operator` instead of the host when given. */
+export const revealResult = (
+  host: web3.Keypair,
+  job: Job,
+  resultHash: Buffer,
+  salt: Buffer,
+  operator?: Operator
+) =>
+  send(
+    [
+      ix(
+        10, // reveal_job_result
+        [
+          w((operator?.signer ?? host).publicKey, true),
+          w(job.address),
+          ...(operator ? [r(operator.resource)] : []),
+        ],
+        resultHash,
+        salt
+      ),
+    ],
+    [operator?.signer ?? host]
+  );
+
+/**
+ * Samples a job whose quorum was just reached for an audit. Waits until the slot after the
+ * quorum reveal shows up in `SlotHashes`.
+ */
+export async function sampleAudit(job: Job) {
+  const revealSlot = await connection.getSlot();
+  while ((await connection.getSlot()) <= revealSlot + 1) {
+    await new Promise((resolve) => setTimeout(resolve, 100));
+  }
+  await send([
+    ix(
+      17, // sample_audit
+      [
+        w(job.address),
+        r(configPda),
+        r(auditorPoolPda),
+        r(web3.SYSVAR_SLOT_HASHES_PUBKEY),
+      ]
+    ),
+  ]);
+}
+
+/** Registers `attester` with the wallet as admin, adding it to the active set. */
+export async function registerAttester(attester: web3.Keypair) {
+  await send([
+    ix(
+      20, // register_attester
+      [
+        w(wallet, true),
+        w(configPda),
+        w(attesterPda(attester.publicKey)),
+        r(SYSTEM_PROGRAM_ID),
+      ],
+      attester.publicKey.toBuffer()
+    ),
+  ]);
+}
+
+export const revokeAttester = (attester: web3.PublicKey) =>
+  send([
+    ix(
+      21, // revoke_attester
+      [w(wallet, true), w(configPda), w(attesterPda(attester))]
+    ),
+  ]);
+
+/**
+ * Records `signer`'s Ed25519-signed statement of the resource's specs, checked against the
+ * registered `attester`, which defaults to the signer.
+ */
+export async function attestResource(
+  resource: web3.PublicKey,
+  signer: web3.Keypair,
+  gpuModel = "A100",
+  attester = signer.publicKey
+) {
+  const statement = Buffer.concat([
+    resource.toBuffer(),
+    str(gpuModel),
+    u8(80), // vram_gb
+    u8(32), // cpu_cores
+    u32(1_000), // compute_rating
+    i64(await chainTime()), // issued_at
+  ]);
+  await send([
+    web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message: statement,
+    }),
+    ix(
+      22, // attest_resource
+      [
+        w(wallet, true),
+        w(resource),
+        r(attesterPda(attester)),
+        r(web3.SYSVAR_INSTRUCTIONS_PUBKEY),
+      ],
+      statement
+    ),
+  ]);
+}
+
+export const issueChallenge = (resource: web3.PublicKey) =>
+  send([
+    ix(
+      23, // issue_challenge
+      [
+        w(wallet, true),
+        r(resource),
+        w(challengePda(resource)),
+        r(web3.SYSVAR_SLOT_HASHES_PUBKEY),
+        r(SYSTEM_PROGRAM_ID),
+      ]
+    ),
+  ]);
+
+export const answerChallenge = (
+  host: web3.Keypair,
+  resource: web3.PublicKey,
+  outputHash: Buffer
+) =>
+  send(
+    [
+      ix(
+        24, // submit_challenge_response
+        [w(host.publicKey, true), r(resource), w(challengePda(resource))],
+        outputHash
+      ),
+    ],
+    [host]
+  );
+
+/** Settles the resource's answered challenge with `attester`'s signed verdict. */
+export async function verifyChallenge(
+  resource: web3.PublicKey,
+  attester: web3.Keypair,
+  outputHash: Buffer,
+  computeRating: number
+) {
+  const challenge = await connection.getAccountInfo(challengePda(resource));
+  const verdict = Buffer.concat([
+    resource.toBuffer(),
+    challenge.data.subarray(40, 72), // seed
+    outputHash,
+    u32(computeRating),
+  ]);
+  await send([
+    web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: attester.secretKey,
+      message: verdict,
+    }),
+    ix(
+      25, // verify_challenge
+      [
+        w(wallet, true),
+        w(resource),
+        w(challengePda(resource)),
+        r(attesterPda(attester.publicKey)),
+        r(web3.SYSVAR_INSTRUCTIONS_PUBKEY),
+      ],
+      verdict
+    ),
+  ]);
+}
+
+export const expireChallenge = (resource: web3.PublicKey) =>
+  send([
+    ix(
+      26, // expire_challenge
+      [w(wallet, true), w(resource), w(challengePda(resource))]
+    ),
+  ]);
+
+/** Status of the resource's challenge, as its `ChallengeStatus` discriminant. */
+export async function challengeStatus(resource: web3.PublicKey) {
+  const challenge = await connection.getAccountInfo(challengePda(resource));
+  return challenge.data[120];
+}
+
+export const auctionPda = (job: Job) => pda("auction", job.address);
+export const bidPda = (job: Job, resource: web3.PublicKey) =>
+  pda("bid", job.address, resource);
+
+/** Posts a job, already funded with `depositEscrow`, for hosts to bid on. */
+export async function postJob(
+  job: Job,
+  deadline: number,
+  biddingEndsAt: number,
+  requirements: Requirements = {}
+) {
+  await send(
+    [
+      ix(
+        27, // post_job
+        [
+          w(job.client.publicKey, true),
+          w(job.address),
+          w(auctionPda(job)),
+          r(job.escrow),
+          r(SYSTEM_PROGRAM_ID),
+        ],
+        u64(job.id),
+        i64(deadline),
+        i64(biddingEndsAt),
+        requirementsData(requirements)
+      ),
+    ],
+    [job.client]
+  );
+}
+
+export const placeBid = (
+  host: web3.Keypair,
+  job: Job,
+  resource: web3.PublicKey,
+  pricePerHour: number
+) =>
+  send(
+    [
+      ix(
+        28, // place_bid
+        [
+          w(host.publicKey, true),
+          r(job.address),
+          w(auctionPda(job)),
+          r(resource),
+          w(bidPda(job, resource)),
+          r(SYSTEM_PROGRAM_ID),
+          r(configPda),
+        ],
+        u64(pricePerHour)
+      ),
+    ],
+    [host]
+  );
+
+/** Accounts of `accept_bid` and `award_job` awarding the job to `resource`'s bid. */
+export const awardAccounts = (
+  signer: web3.PublicKey,
+  job: Job,
+  resource: web3.PublicKey,
+  clientToken: web3.PublicKey
+) => [
+  w(signer, true),
+  w(job.address),
+  r(auctionPda(job)),
+  r(bidPda(job, resource)),
+  w(resource),
+  w(job.escrow),
+  w(job.vault),
+  w(clientToken),
+  r(job.mint.tokenProgram),
+  r(configPda),
+  r(job.mint.address),
+];
+
+/** Creates the order book for `gpuClass`. */
+export async function createOrderBook(gpuClass: string) {
+  const book = pda("book", gpuClass);
+  await send([
+    ix(
+      32, // create_order_book
+      [w(wallet, true), w(book), r(SYSTEM_PROGRAM_ID)],
+      Buffer.from(gpuClass)
+    ),
+  ]);
+  return book;
+}
+
+export const postAsk = (
+  host: web3.Keypair,
+  book: web3.PublicKey,
+  resource: web3.PublicKey,
+  pricePerHour: number,
+  hours: number
+) =>
+  send(
+    [
+      ix(
+        33, // post_ask
+        [w(host.publicKey, true), w(book), r(resource)],
+        u64(pricePerHour),
+        u32(hours)
+      ),
+    ],
+    [host]
+  );
+
+export const leasePda = (resource: web3.PublicKey, start: number) =>
+  pda("lease", resource, i64(start));
+export const leaseVaultPda = (lease: web3.PublicKey) =>
+  pda("lease_vault", lease);
+
+/**
+ * Leases `resource` to `client` for four periods of 100 seconds at 500 each, from `start`,
+ * with a 10% penalty for either side terminating early.
+ */
+export const createLease = (
+  client: web3.Keypair,
+  host: web3.Keypair,
+  resource: web3.PublicKey,
+  clientToken: web3.PublicKey,
+  mint: Mint,
+  start: number
+) => {
+  const lease = leasePda(resource, start);
+  return send(
+    [
+      ix(
+        37, // create_lease
+        [
+          w(client.publicKey, true),
+          w(host.publicKey, true),
+          w(resource),
+          w(lease),
+          w(leaseVaultPda(lease)),
+          w(clientToken),
+          r(mint.tokenProgram),
+          r(SYSTEM_PROGRAM_ID),
+          r(mint.address),
+          r(configPda),
+        ],
+        i64(start),
+        i64(start + 400),
+        i64(100),
+        u64(500),
+        u16(1_000),
+        u16(1_000)
+      ),
+    ],
+    [client, host]
+  );
+};
+
+export const creditPda = (owner: web3.PublicKey) => pda("credit", owner);
+export const creditVaultPda = (credit: web3.PublicKey) =>
+  pda("credit_vault", credit);
+
+/** Tops up the credit vault of `owner`, which defaults to the payer. */
+export async function depositCredit(
+  p: Protocol,
+  payer: web3.Keypair,
+  tokenAccount: web3.PublicKey,
+  amount: number,
+  owner?: web3.PublicKey
+) {
+  const credit = creditPda(owner ?? payer.publicKey);
+  await send(
+    [
+      ix(
+        40, // deposit_credit
+        [
+          w(payer.publicKey, true),
+          w(credit),
+          w(creditVaultPda(credit)),
+          w(tokenAccount),
+          r(TOKEN_PROGRAM_ID),
+          r(SYSTEM_PROGRAM_ID),
+          r(configPda),
+          r(p.flux.address),
+        ],
+        u64(amount),
+        ...(owner ? [owner.toBuffer()] : [])
+      ),
+    ],
+    [payer]
+  );
+}
+
+/** Accounts `start_job` takes to fund a FLUX job from `owner`'s credit vault. */
+export const creditFundingAccounts = (
+  p: Protocol,
+  job: Job,
+  owner: web3.PublicKey
+) => {
+  const credit = creditPda(owner);
+  return [
+    w(credit),
+    w(creditVaultPda(credit)),
+    r(TOKEN_PROGRAM_ID),
+    w(job.vault),
+    r(p.flux.address),
+  ];
+};
+
+export const addOperator = (
+  host: web3.Keypair,
+  resource: web3.PublicKey,
+  operator: web3.PublicKey,
+  expiresAt: number
+) =>
+  send(
+    [
+      ix(
+        45, // add_operator
+        [w(host.publicKey, true), w(resource)],
+        operator.toBuffer(),
+        i64(expiresAt)
+      ),
+    ],
+    [host]
+  );
+
+export const revokeOperator = (
+  host: web3.Keypair,
+  resource: web3.PublicKey,
+  operator: web3.PublicKey
+) =>
+  send(
+    [
+      ix(
+        46, // revoke_operator
+        [w(host.publicKey, true), w(resource)],
+        operator.toBuffer()
+      ),
+    ],
+    [host]
+  );
+
+export const heartbeat = (signer: web3.Keypair, resource: web3.PublicKey) =>
+  send(
+    [
+      ix(
+        47, // heartbeat
+        [w(signer.publicKey, true), w(resource)]
+      ),
+    ],
+    [signer]
+  );
+
+/** Delegates `amount` of fresh FLUX to `resource`, returning the delegation account. */
+export async function delegateStake(
+  p: Protocol,
+  delegator: web3.Keypair,
+  resource: web3.PublicKey,
+  amount: number
+) {
+  const delegation = pda("delegation", resource, delegator.publicKey);
+  const tokenAccount = await createTokenAccount(
+    p.flux,
+    delegator.publicKey,
+    amount
+  );
+  await send(
+    [
+      ix(
+        56, // delegate_stake
+        [
+          w(delegator.publicKey, true),
+          w(resource),
+          w(delegation),
+          w(tokenAccount),
+          r(TOKEN_PROGRAM_ID),
+          r(SYSTEM_PROGRAM_ID),
+          w(rewardPoolPda),
+          w(stakePda(resource)),
+          r(p.flux.address),
+        ],
+        u64(amount)
+      ),
+    ],
+    [delegator]
+  );
+  return delegation;
+}
+
+export const claimInsurance = (
+  p: Protocol,
+  client: web3.Keypair,
+  job: Job,
+  clientFlux: web3.PublicKey
+) =>
+  send(
+    [
+      ix(
+        65, // claim_insurance
+        [
+          w(client.publicKey, true),
+          w(job.address),
+          r(configPda),
+          w(insurancePda),
+          w(clientFlux),
+          r(TOKEN_PROGRAM_ID),
+          r(p.flux.address),
+        ]
+      ),
+    ],
+    [client]
+  );
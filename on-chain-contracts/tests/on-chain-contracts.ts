@@ -1,6 +1,92 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Program, web3 } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { createHash } from "crypto";
 import { OnChainContracts } from "../target/types/on_chain_contracts";
+import {
+  addOperator,
+  answerChallenge,
+  Assignment,
+  attestResource,
+  auctionPda,
+  auditorPoolPda,
+  awardAccounts,
+  balance,
+  bidPda,
+  bool,
+  chainTime,
+  challengeStatus,
+  checkpointPda,
+  claimInsurance,
+  commitResult,
+  configData,
+  configPda,
+  connection,
+  createLease,
+  createMint,
+  createOrderBook,
+  createTokenAccount,
+  creditFundingAccounts,
+  creditPda,
+  creditVaultPda,
+  delegateStake,
+  depositCredit,
+  depositEscrow,
+  ESCROW_ACCOUNT_SPACE,
+  expectError,
+  expireChallenge,
+  expireJob,
+  fundedKeypair,
+  FUNDING_CREDIT,
+  FUNDING_ORG,
+  heartbeat,
+  i32,
+  i64,
+  insurancePda,
+  issueChallenge,
+  ix,
+  JOB_ACCOUNT_SPACE,
+  jobAccounts,
+  leasePda,
+  leaseVaultPda,
+  Mint,
+  mintToIx,
+  openJob,
+  pda,
+  placeBid,
+  postAsk,
+  postJob,
+  priceFeedPda,
+  PROGRAM_ID,
+  programDataPda,
+  r,
+  readU64,
+  registerAttester,
+  registerResource,
+  Requirements,
+  revealResult,
+  revokeAttester,
+  revokeOperator,
+  rewardPoolPda,
+  sampleAudit,
+  send,
+  settleJob,
+  setupProtocol,
+  stakeFlux,
+  stakePda,
+  startJob,
+  SYSTEM_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  u16,
+  u32,
+  u64,
+  u8,
+  unstakeFlux,
+  verifyChallenge,
+  w,
+  waitPast,
+  wallet,
+} from "./helpers";
 
 describe("on-chain-contracts", () => {
   // Configure the client to use the local cluster.
@@ -13,4 +99,1501 @@ describe("on-chain-contracts", () => {
     const tx = await program.methods.initialize().rpc();
     console.log("Your transaction signature", tx);
   });
+
+  describe("expire_job", () => {
+    it("refunds the client and slashes a silent host's stake", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 10_000);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 5_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 10_000);
+
+      const deadline = (await chainTime()) + 4;
+      const job = await openJob(p, client, clientFlux, 5_000, [resource], {
+        deadline,
+      });
+      // The stake backing the job cannot be pulled out ahead of the slash.
+      await expectError(
+        unstakeFlux(p, host, resource, hostFlux, 10_000),
+        4 // InvalidJobStatus
+      );
+      await waitPast(deadline);
+
+      const insuranceBefore = await balance(insurancePda);
+      await expireJob(p, job, clientFlux, clientFlux, [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ]);
+
+      // 5% of the stake is slashed: 100 to the insurance pool, 400 to the client
+      // on top of the full refund.
+      expect(await balance(clientFlux)).to.equal(5_000 + 400);
+      expect((await balance(insurancePda)) - insuranceBefore).to.equal(100);
+      expect(await balance(stakePda(resource))).to.equal(9_500);
+    });
+  });
+
+  describe("reveal_job_result", () => {
+    it("rejects a reveal that does not match the commitment", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+      const job = await openJob(p, client, clientFlux, 1_000, [resource], {
+        deadline: (await chainTime()) + 600,
+      });
+
+      const result = Buffer.alloc(32, 7);
+      const salt = Buffer.alloc(32, 1);
+      await commitResult(host, job, result, salt);
+
+      await expectError(
+        revealResult(host, job, result, Buffer.alloc(32, 2)),
+        12 // CommitmentMismatch
+      );
+      await expectError(
+        revealResult(host, job, Buffer.alloc(32, 8), salt),
+        12 // CommitmentMismatch
+      );
+      await revealResult(host, job, result, salt);
+    });
+  });
+
+  describe("settle_job", () => {
+    it("pays the agreeing hosts and refunds the dissenter's share", async () => {
+      const p = await setupProtocol();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 3_000);
+      const assignments: Assignment[] = [];
+      const hosts = [];
+      for (let i = 0; i < 3; i++) {
+        const host = await fundedKeypair();
+        hosts.push(host);
+        assignments.push({
+          host: host.publicKey,
+          resource: await registerResource(host),
+          tokenAccount: await createTokenAccount(p.flux, host.publicKey),
+        });
+      }
+      const job = await openJob(
+        p,
+        client,
+        clientFlux,
+        3_000,
+        assignments.map((a) => a.resource),
+        { deadline: (await chainTime()) + 600, quorum: 2 }
+      );
+
+      const agreed = Buffer.alloc(32, 7);
+      const dissent = Buffer.alloc(32, 8);
+      const salt = Buffer.alloc(32, 1);
+      const results = [agreed, agreed, dissent];
+      for (let i = 0; i < 3; i++) {
+        await commitResult(hosts[i], job, results[i], salt);
+      }
+      for (let i = 0; i < 3; i++) {
+        await revealResult(hosts[i], job, results[i], salt);
+      }
+      await sampleAudit(job);
+
+      const treasuryBefore = await balance(p.treasury);
+      const insuranceBefore = await balance(insurancePda);
+      await settleJob(job, clientFlux, p.treasury, assignments);
+
+      // Each agreeing host earns 1_000 less the 5% fee; 20% of the 100 in fees is
+      // insured, and the dissenter's 1_000 goes back to the client.
+      expect(await balance(assignments[0].tokenAccount)).to.equal(950);
+      expect(await balance(assignments[1].tokenAccount)).to.equal(950);
+      expect(await balance(assignments[2].tokenAccount)).to.equal(0);
+      expect((await balance(p.treasury)) - treasuryBefore).to.equal(80);
+      expect((await balance(insurancePda)) - insuranceBefore).to.equal(20);
+      expect(await balance(clientFlux)).to.equal(1_000);
+      expect(await balance(job.vault)).to.equal(0);
+    });
+  });
+
+  describe("initialize_config", () => {
+    it("only lets the upgrade authority create the config", async () => {
+      const p = await setupProtocol();
+      const stranger = await fundedKeypair();
+      await expectError(
+        send(
+          [
+            ix(
+              12, // initialize_config
+              [
+                w(stranger.publicKey, true),
+                w(configPda),
+                w(auditorPoolPda),
+                r(SYSTEM_PROGRAM_ID),
+                r(programDataPda),
+              ],
+              configData(p)
+            ),
+          ],
+          [stranger]
+        ),
+        18 // UnauthorizedAdmin
+      );
+    });
+  });
+
+  describe("update_config", () => {
+    it("keeps the FLUX mint fixed", async () => {
+      const p = await setupProtocol();
+      const otherFlux = await createMint(6);
+      await expectError(
+        send([
+          ix(
+            13, // update_config
+            [w(wallet, true), w(configPda)],
+            configData({ ...p, flux: otherFlux })
+          ),
+        ]),
+        54 // MintMismatch
+      );
+    });
+  });
+
+  describe("submit_audit", () => {
+    const updateConfig = async (auditRateBps: number) =>
+      send([
+        ix(
+          13, // update_config
+          [w(wallet, true), w(configPda)],
+          configData(await setupProtocol(), auditRateBps)
+        ),
+      ]);
+
+    // Every quorum is sampled while this block runs.
+    before(() => updateConfig(10_000));
+    after(() => updateConfig(0));
+
+    it("pays the protocol fee to an auditor that confirms the result", async () => {
+      const p = await setupProtocol();
+      const auditor = await fundedKeypair();
+      const auditorResource = await registerResource(auditor);
+      const auditorFlux = await createTokenAccount(p.flux, auditor.publicKey);
+      await send(
+        [
+          ix(
+            14, // join_auditor_pool
+            [
+              w(auditor.publicKey, true),
+              r(auditorResource),
+              r(configPda),
+              w(auditorPoolPda),
+            ]
+          ),
+        ],
+        [auditor]
+      );
+
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+      const job = await openJob(p, client, clientFlux, 1_000, [resource], {
+        deadline: (await chainTime()) + 600,
+      });
+      const result = Buffer.alloc(32, 7);
+      const salt = Buffer.alloc(32, 1);
+      await commitResult(host, job, result, salt);
+      await revealResult(host, job, result, salt);
+      await expectError(
+        settleJob(
+          job,
+          clientFlux,
+          p.treasury,
+          [{ host: host.publicKey, resource, tokenAccount: hostFlux }],
+          auditorFlux
+        ),
+        24 // AuditNotReady
+      );
+      await sampleAudit(job);
+
+      await send(
+        [
+          ix(
+            16, // submit_audit
+            [
+              w(auditor.publicKey, true),
+              w(job.address),
+              r(auditorResource),
+              r(configPda),
+            ],
+            result
+          ),
+        ],
+        [auditor]
+      );
+      await settleJob(
+        job,
+        clientFlux,
+        p.treasury,
+        [{ host: host.publicKey, resource, tokenAccount: hostFlux }],
+        auditorFlux
+      );
+
+      // The 50 fee goes to the auditor instead of the treasury, less the insurance cut.
+      expect(await balance(hostFlux)).to.equal(950);
+      expect(await balance(auditorFlux)).to.equal(40);
+    });
+  });
+
+  describe("accept_checkpoint", () => {
+    it("pays an expired job's host for the accepted progress", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 5_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+
+      const deadline = (await chainTime()) + 8;
+      const job = await openJob(p, client, clientFlux, 5_000, [resource], {
+        deadline,
+      });
+
+      const leaves = [Buffer.alloc(32, 1), Buffer.alloc(32, 2)];
+      const root = createHash("sha256")
+        .update(Buffer.concat(leaves))
+        .digest();
+      const checkpoint = checkpointPda(job.address, host.publicKey);
+      await send(
+        [
+          ix(
+            18, // submit_checkpoint
+            [
+              w(host.publicKey, true),
+              r(job.address),
+              w(checkpoint),
+              r(SYSTEM_PROGRAM_ID),
+            ],
+            u64(1),
+            u64(100),
+            root
+          ),
+        ],
+        [host]
+      );
+      const acceptCheckpoint = (leaf: Buffer, sibling: Buffer) =>
+        send(
+          [
+            ix(
+              19, // accept_checkpoint
+              [w(client.publicKey, true), r(job.address), w(checkpoint)],
+              u16(0),
+              leaf,
+              sibling
+            ),
+          ],
+          [client]
+        );
+      await expectError(
+        acceptCheckpoint(leaves[0], Buffer.alloc(32, 3)),
+        27 // InvalidCheckpointProof
+      );
+      await acceptCheckpoint(leaves[0], leaves[1]);
+
+      await waitPast(deadline);
+      await expireJob(p, job, clientFlux, clientFlux, [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ]);
+
+      // The host is paid for the time its checkpoint covers and the client gets the
+      // rest of the escrow, plus its 40 share of the host's 50 slash.
+      const paid = await balance(hostFlux);
+      expect(paid).to.be.greaterThan(0);
+      expect(paid).to.be.lessThan(5_000);
+      expect(paid + (await balance(clientFlux))).to.equal(5_000 + 40);
+    });
+  });
+
+  describe("attest_resource", () => {
+    it("only accepts statements signed by the registered attester", async () => {
+      await setupProtocol();
+      const host = await fundedKeypair();
+      const resource = await registerResource(host);
+      const attester = web3.Keypair.generate();
+      const impostor = web3.Keypair.generate();
+      await registerAttester(attester);
+
+      await expectError(
+        attestResource(resource, impostor, "A100", attester.publicKey),
+        28 // InvalidAttestation
+      );
+      await attestResource(resource, attester);
+    });
+
+    it("ignores attestations once their attester is revoked", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 2_000);
+      const resource = await registerResource(host, { gpuCount: 2 });
+      const attester = web3.Keypair.generate();
+      await registerAttester(attester);
+      await attestResource(resource, attester);
+
+      const opts = {
+        deadline: (await chainTime()) + 600,
+        requirements: { requireAttested: true },
+      };
+      await openJob(p, client, clientFlux, 1_000, [resource], opts);
+
+      await revokeAttester(attester.publicKey);
+      await expectError(
+        openJob(p, client, clientFlux, 1_000, [resource], { ...opts, id: 2 }),
+        34 // RequirementsNotMet
+      );
+      await expectError(
+        attestResource(resource, attester),
+        28 // InvalidAttestation
+      );
+    });
+  });
+
+  describe("verify_challenge", () => {
+    // `ChallengeStatus` discriminants.
+    const PASSED = 2;
+    const FAILED = 3;
+
+    it("passes a challenge whose output matches the attester's verdict", async () => {
+      await setupProtocol();
+      const host = await fundedKeypair();
+      const resource = await registerResource(host);
+      const attester = web3.Keypair.generate();
+      await registerAttester(attester);
+
+      await issueChallenge(resource);
+      const output = Buffer.alloc(32, 5);
+      await answerChallenge(host, resource, output);
+      await verifyChallenge(resource, attester, output, 1_200);
+
+      expect(await challengeStatus(resource)).to.equal(PASSED);
+    });
+
+    it("fails a challenge whose output differs from the verdict", async () => {
+      await setupProtocol();
+      const host = await fundedKeypair();
+      const resource = await registerResource(host);
+      const attester = web3.Keypair.generate();
+      await registerAttester(attester);
+
+      await issueChallenge(resource);
+      await answerChallenge(host, resource, Buffer.alloc(32, 5));
+      await verifyChallenge(resource, attester, Buffer.alloc(32, 6), 1_200);
+
+      expect(await challengeStatus(resource)).to.equal(FAILED);
+    });
+  });
+
+  describe("expire_challenge", () => {
+    it("leaves a challenge open until its deadline", async () => {
+      const host = await fundedKeypair();
+      const resource = await registerResource(host);
+      await issueChallenge(resource);
+
+      await expectError(expireChallenge(resource), 31); // ChallengeNotDue
+      await expectError(issueChallenge(resource), 31); // ChallengeNotDue
+      await answerChallenge(host, resource, Buffer.alloc(32, 5));
+      await expectError(expireChallenge(resource), 31); // ChallengeNotDue
+    });
+  });
+
+  describe("gpu capacity", () => {
+    it("frees a job's GPUs once it settles", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 3_000);
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey);
+      const resource = await registerResource(host, { gpuCount: 2 });
+      const deadline = (await chainTime()) + 600;
+
+      const job = await openJob(p, client, clientFlux, 2_000, [resource], {
+        deadline,
+        requirements: { gpuCount: 2 },
+      });
+      await expectError(
+        openJob(p, client, clientFlux, 1_000, [resource], { id: 2, deadline }),
+        33 // InsufficientCapacity
+      );
+
+      const result = Buffer.alloc(32, 7);
+      const salt = Buffer.alloc(32, 1);
+      await commitResult(host, job, result, salt);
+      await revealResult(host, job, result, salt);
+      await sampleAudit(job);
+      await settleJob(job, clientFlux, p.treasury, [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ]);
+
+      await startJob(client, jobAccounts(client, 2, p.flux), [resource], {
+        deadline,
+      });
+    });
+  });
+
+  describe("job requirements", () => {
+    it("only starts on a resource that meets every requirement", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      // An A100 with 80 GB, 32 cores and a 1_000 rating at 1_000 per hour.
+      const resource = await registerResource(host);
+      const job = jobAccounts(client, 1, p.flux);
+      await depositEscrow(job, clientFlux, 1_000);
+      const deadline = (await chainTime()) + 600;
+      const start = (requirements: Requirements) =>
+        startJob(client, job, [resource], { deadline, requirements });
+
+      for (const requirements of [
+        { allowedGpuModels: ["H100"] },
+        { minVramGb: 96 },
+        { minComputeRating: 2_000 },
+        { maxPricePerHour: 500 },
+        { requireAttested: true },
+      ]) {
+        await expectError(start(requirements), 34); // RequirementsNotMet
+      }
+      await start({ allowedGpuModels: ["H100", "A100"], minVramGb: 80 });
+    });
+  });
+
+  describe("accept_bid", () => {
+    it("refunds the budget above the winning bid", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 4_000);
+      const resource = await registerResource(host);
+
+      // Two hours at up to 2_000 an hour.
+      const job = jobAccounts(client, 1, p.flux);
+      await depositEscrow(job, clientFlux, 4_000);
+      const now = await chainTime();
+      await postJob(job, now + 600, now + 300, {
+        durationHours: 2,
+        maxPricePerHour: 2_000,
+      });
+      await placeBid(host, job, resource, 1_500);
+      await send(
+        [
+          ix(
+            29, // accept_bid
+            awardAccounts(client.publicKey, job, resource, clientFlux)
+          ),
+        ],
+        [client]
+      );
+
+      expect(await balance(job.vault)).to.equal(3_000);
+      expect(await balance(clientFlux)).to.equal(1_000);
+    });
+  });
+
+  describe("drop_bid", () => {
+    it("hands the award to the next bid once the best one goes offline", async () => {
+      const p = await setupProtocol();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 2_000);
+      const cheap = await fundedKeypair();
+      const cheapResource = await registerResource(cheap);
+      const other = await fundedKeypair();
+      const otherResource = await registerResource(other);
+
+      const job = jobAccounts(client, 1, p.flux);
+      await depositEscrow(job, clientFlux, 2_000);
+      const biddingEndsAt = (await chainTime()) + 4;
+      await postJob(job, biddingEndsAt + 600, biddingEndsAt, {
+        maxPricePerHour: 2_000,
+      });
+      await placeBid(cheap, job, cheapResource, 1_000);
+      await placeBid(other, job, otherResource, 1_500);
+
+      const dropBid = () =>
+        send([
+          ix(
+            31, // drop_bid
+            [
+              w(wallet, true),
+              r(job.address),
+              w(auctionPda(job)),
+              w(bidPda(job, cheapResource)),
+              r(cheapResource),
+              r(configPda),
+              r(bidPda(job, otherResource)),
+            ]
+          ),
+        ]);
+      await expectError(dropBid(), 39); // BidStillAwardable
+
+      await send(
+        [
+          ix(
+            1, // update_resource_status
+            [w(cheap.publicKey, true), w(cheapResource)],
+            u8(2) // Offline
+          ),
+        ],
+        [cheap]
+      );
+      await waitPast(biddingEndsAt);
+      const award = (resource: web3.PublicKey) =>
+        send([
+          ix(
+            30, // award_job
+            awardAccounts(wallet, job, resource, clientFlux)
+          ),
+        ]);
+      await expectError(award(cheapResource), 33); // InsufficientCapacity
+
+      const closeBid = () =>
+        send(
+          [
+            ix(
+              72, // close_bid
+              [
+                w(other.publicKey, true),
+                r(job.address),
+                w(bidPda(job, otherResource)),
+              ]
+            ),
+          ],
+          [other]
+        );
+      await expectError(closeBid(), 4); // InvalidJobStatus
+
+      await dropBid();
+      await award(otherResource);
+      expect(await balance(job.vault)).to.equal(1_500);
+      expect(await balance(clientFlux)).to.equal(500);
+
+      await closeBid();
+      expect(await connection.getAccountInfo(bidPda(job, otherResource))).to.be
+        .null;
+    });
+  });
+
+  describe("cancel_order", () => {
+    it("refunds a cancelled bid's escrow and prefunded job rent", async () => {
+      const p = await setupProtocol();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 2_000);
+      // A fresh book, so the bid is its first order.
+      const book = await createOrderBook(
+        `H100-${client.publicKey.toBase58().slice(0, 8)}`
+      );
+      const job = jobAccounts(client, 1, p.flux);
+      await depositEscrow(job, clientFlux, 2_000);
+      await send(
+        [
+          ix(
+            34, // post_bid
+            [
+              w(client.publicKey, true),
+              w(book),
+              w(job.escrow),
+              r(configPda),
+              w(job.address),
+              r(SYSTEM_PROGRAM_ID),
+            ],
+            u64(job.id),
+            u64(1_000),
+            u32(2)
+          ),
+        ],
+        [client]
+      );
+      expect(await connection.getBalance(job.address)).to.equal(
+        await connection.getMinimumBalanceForRentExemption(JOB_ACCOUNT_SPACE)
+      );
+
+      await send(
+        [
+          ix(
+            35, // cancel_order
+            [
+              w(client.publicKey, true),
+              w(book),
+              w(job.escrow),
+              w(job.vault),
+              w(clientFlux),
+              r(TOKEN_PROGRAM_ID),
+              w(job.address),
+              r(SYSTEM_PROGRAM_ID),
+              r(p.flux.address),
+            ],
+            u64(0)
+          ),
+        ],
+        [client]
+      );
+
+      expect(await balance(clientFlux)).to.equal(2_000);
+      expect(await connection.getBalance(job.address)).to.equal(0);
+    });
+  });
+
+  describe("post_ask", () => {
+    it("evicts the worst-priced ask from a full book for a cheaper one", async () => {
+      const host = await fundedKeypair();
+      const gpuClass = `A10-${host.publicKey.toBase58().slice(0, 8)}`;
+      const book = await createOrderBook(gpuClass);
+      const resource = await registerResource(host, { gpuModel: gpuClass });
+
+      const MAX_ORDERS = 32;
+      for (let i = 0; i < MAX_ORDERS; i++) {
+        await postAsk(host, book, resource, 2_000, 1);
+      }
+      await expectError(postAsk(host, book, resource, 2_000, 1), 41); // OrderBookFull
+      await postAsk(host, book, resource, 1_500, 1);
+    });
+  });
+
+  describe("terminate_lease", () => {
+    it("pays the host the client's penalty on early termination", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 2_000);
+      const resource = await registerResource(host);
+
+      // Four periods of 500, starting in ten minutes, with a 10% client penalty.
+      const start = (await chainTime()) + 600;
+      const lease = leasePda(resource, start);
+      const vault = leaseVaultPda(lease);
+      await createLease(client, host, resource, clientFlux, p.flux, start);
+      expect(await balance(vault)).to.equal(2_000);
+
+      const closeLease = () =>
+        send(
+          [
+            ix(
+              73, // close_lease
+              [
+                w(client.publicKey, true),
+                w(lease),
+                w(vault),
+                r(TOKEN_PROGRAM_ID),
+                r(p.flux.address),
+              ]
+            ),
+          ],
+          [client]
+        );
+      await expectError(closeLease(), 60); // LeaseStillActive
+
+      await send(
+        [
+          ix(
+            39, // terminate_lease
+            [
+              w(client.publicKey, true),
+              w(lease),
+              w(vault),
+              w(resource),
+              w(clientFlux),
+              w(hostFlux),
+              r(TOKEN_PROGRAM_ID),
+              w(stakePda(resource)),
+              w(rewardPoolPda),
+              r(p.flux.address),
+              r(configPda),
+              r(p.flux.address),
+              w(clientFlux),
+            ]
+          ),
+        ],
+        [client]
+      );
+
+      expect(await balance(hostFlux)).to.equal(200);
+      expect(await balance(clientFlux)).to.equal(1_800);
+
+      await closeLease();
+      expect(await connection.getAccountInfo(lease)).to.be.null;
+      expect(await connection.getAccountInfo(vault)).to.be.null;
+    });
+  });
+
+  describe("create_lease", () => {
+    it("keeps other clients' jobs and the host's stake out of the lease", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const other = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 2_000);
+      const otherFlux = await createTokenAccount(p.flux, other.publicKey, 2_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+
+      const start = (await chainTime()) + 600;
+      const unlisted = await createMint(6);
+      const clientUnlisted = await createTokenAccount(
+        unlisted,
+        client.publicKey,
+        2_000
+      );
+      await expectError(
+        createLease(client, host, resource, clientUnlisted, unlisted, start),
+        53 // MintNotAllowed
+      );
+      await createLease(client, host, resource, clientFlux, p.flux, start);
+      await expectError(
+        unstakeFlux(p, host, resource, hostFlux, 1_000),
+        43 // ResourceLeased
+      );
+
+      // A job running into the lease is refused, one ending before it is not.
+      await expectError(
+        openJob(p, other, otherFlux, 1_000, [resource], { deadline: start + 1 }),
+        43 // ResourceLeased
+      );
+      await startJob(other, jobAccounts(other, 1, p.flux), [resource], {
+        deadline: start,
+      });
+    });
+  });
+
+  describe("deposit_credit", () => {
+    it("funds a job's escrow from the client's credit vault", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 3_000);
+      const resource = await registerResource(host);
+      await depositCredit(p, client, clientFlux, 3_000);
+      const credit = creditPda(client.publicKey);
+      const creditVault = creditVaultPda(credit);
+
+      const job = jobAccounts(client, 1, p.flux);
+      await startJob(
+        client,
+        job,
+        [resource],
+        { deadline: (await chainTime()) + 600 },
+        FUNDING_CREDIT,
+        creditFundingAccounts(p, job, client.publicKey)
+      );
+      expect(await balance(job.vault)).to.equal(1_000);
+      expect(await balance(creditVault)).to.equal(2_000);
+
+      const withdrawCredit = (amount: number) =>
+        send(
+          [
+            ix(
+              41, // withdraw_credit
+              [
+                w(client.publicKey, true),
+                w(credit),
+                w(creditVault),
+                w(clientFlux),
+                r(TOKEN_PROGRAM_ID),
+                r(p.flux.address),
+              ],
+              u64(amount)
+            ),
+          ],
+          [client]
+        );
+      await expectError(withdrawCredit(2_001), 5); // InsufficientFunds
+      await withdrawCredit(2_000);
+      expect(await balance(clientFlux)).to.equal(2_000);
+      expect(await balance(creditVault)).to.equal(0);
+    });
+  });
+
+  describe("create_org", () => {
+    // An organization funded with 3_000 FLUX whose member may spend `limit` per period.
+    const setupOrg = async (limit: number) => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const admin = await fundedKeypair();
+      const member = await fundedKeypair();
+      const adminFlux = await createTokenAccount(p.flux, admin.publicKey, 3_000);
+      const resource = await registerResource(host);
+
+      const org = pda("org", admin.publicKey, u64(1));
+      const membership = pda("member", org, member.publicKey);
+      await send(
+        [
+          ix(
+            42, // create_org
+            [w(admin.publicKey, true), w(org), r(SYSTEM_PROGRAM_ID)],
+            u64(1),
+            i64(86_400),
+            u64(10_000),
+            u32(0) // allowed_hosts: any
+          ),
+        ],
+        [admin]
+      );
+      await depositCredit(p, admin, adminFlux, 3_000, org);
+      const setMemberLimit = (limit: number) =>
+        send(
+          [
+            ix(
+              44, // set_org_member
+              [
+                w(admin.publicKey, true),
+                r(org),
+                w(membership),
+                r(SYSTEM_PROGRAM_ID),
+              ],
+              member.publicKey.toBuffer(),
+              u64(limit),
+              bool(true)
+            ),
+          ],
+          [admin]
+        );
+      await setMemberLimit(limit);
+
+      const job = jobAccounts(member, 1, p.flux);
+      const startOrgJob = (deadline: number) =>
+        startJob(member, job, [resource], { deadline }, FUNDING_ORG, [
+          ...creditFundingAccounts(p, job, org),
+          w(org),
+          w(membership),
+        ]);
+      return {
+        p,
+        host,
+        resource,
+        org,
+        job,
+        setMemberLimit,
+        startOrgJob,
+      };
+    };
+
+    it("charges an organization's jobs to its member budgets", async () => {
+      // The job costs 1_000, over the member's limit of 500.
+      const { org, job, setMemberLimit, startOrgJob } = await setupOrg(500);
+      await expectError(startOrgJob((await chainTime()) + 600), 46); // SpendLimitExceeded
+
+      await setMemberLimit(1_000);
+      await startOrgJob((await chainTime()) + 600);
+      expect(await balance(job.vault)).to.equal(1_000);
+      expect(await balance(creditVaultPda(creditPda(org)))).to.equal(2_000);
+    });
+
+    it("refunds an organization's expired job into its credit", async () => {
+      const { p, host, resource, org, job, startOrgJob } =
+        await setupOrg(1_000);
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+      const credit = creditPda(org);
+      const creditVault = creditVaultPda(credit);
+
+      const deadline = (await chainTime()) + 4;
+      await startOrgJob(deadline);
+      await waitPast(deadline);
+      const assignments = [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ];
+      await expireJob(p, job, creditVault, creditVault, assignments, credit);
+
+      // The refund and the client's part of the slash went back to the organization.
+      const refunded = await balance(creditVault);
+      expect(refunded).to.be.greaterThan(3_000);
+      // `CreditAccount::balance` follows the owner key.
+      const creditData = (await connection.getAccountInfo(credit)).data;
+      expect(creditData.readUInt32LE(32)).to.equal(refunded);
+    });
+  });
+
+  describe("add_operator", () => {
+    it("lets an operator key act for its host until revoked", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const worker = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+
+      const expiresAt = (await chainTime()) + 600;
+      await expectError(
+        addOperator(worker, resource, worker.publicKey, expiresAt),
+        2 // UnauthorizedHost
+      );
+      await expectError(heartbeat(worker, resource), 2); // UnauthorizedHost
+      await addOperator(host, resource, worker.publicKey, expiresAt);
+      await heartbeat(worker, resource);
+
+      // Results are committed and revealed under the host's key.
+      const job = await openJob(p, client, clientFlux, 1_000, [resource], {
+        deadline: (await chainTime()) + 600,
+      });
+      const result = Buffer.alloc(32, 7);
+      const salt = Buffer.alloc(32, 1);
+      const operator = { signer: worker, resource };
+      await expectError(
+        commitResult(host, job, result, salt, { signer: client, resource }),
+        3 // UnauthorizedHost
+      );
+      await commitResult(host, job, result, salt, operator);
+      await revealResult(host, job, result, salt, operator);
+
+      await revokeOperator(host, resource, worker.publicKey);
+      await expectError(heartbeat(worker, resource), 2); // UnauthorizedHost
+    });
+
+    it("stops accepting an operator key once it expires", async () => {
+      const host = await fundedKeypair();
+      const worker = await fundedKeypair();
+      const resource = await registerResource(host);
+
+      const expiresAt = (await chainTime()) + 4;
+      await addOperator(host, resource, worker.publicKey, expiresAt);
+      await heartbeat(worker, resource);
+      await waitPast(expiresAt);
+      await expectError(heartbeat(worker, resource), 2); // UnauthorizedHost
+    });
+  });
+
+  describe("unstake_fleet", () => {
+    it("keeps unstaked fleet FLUX in the pool while it unbonds", async () => {
+      const p = await setupProtocol();
+      const operator = await fundedKeypair();
+      const operatorFlux = await createTokenAccount(p.flux, operator.publicKey, 1_000);
+      const fleet = pda("fleet", operator.publicKey, u64(1));
+      const staking = stakePda(fleet);
+      await send(
+        [
+          ix(
+            48, // create_fleet
+            [
+              w(operator.publicKey, true),
+              w(fleet),
+              r(SYSTEM_PROGRAM_ID),
+              w(staking),
+              r(TOKEN_PROGRAM_ID),
+              r(configPda),
+              r(p.flux.address),
+            ],
+            u64(1)
+          ),
+          ix(
+            51, // stake_fleet
+            [
+              w(operator.publicKey, true),
+              w(fleet),
+              w(operatorFlux),
+              r(TOKEN_PROGRAM_ID),
+              w(staking),
+              r(p.flux.address),
+            ],
+            u64(1_000)
+          ),
+        ],
+        [operator]
+      );
+
+      const unstake = (amount: number) =>
+        send(
+          [
+            ix(
+              52, // unstake_fleet
+              [w(operator.publicKey, true), w(fleet), r(staking)],
+              u64(amount)
+            ),
+          ],
+          [operator]
+        );
+      await expectError(unstake(1_001), 5); // InsufficientFunds
+      await unstake(400);
+      await expectError(
+        send(
+          [
+            ix(
+              54, // withdraw_fleet_unbonded
+              [
+                w(operator.publicKey, true),
+                w(fleet),
+                w(staking),
+                w(operatorFlux),
+                r(TOKEN_PROGRAM_ID),
+                r(p.flux.address),
+              ]
+            ),
+          ],
+          [operator]
+        ),
+        50 // StillUnbonding
+      );
+      expect(await balance(staking)).to.equal(1_000);
+      expect(await balance(operatorFlux)).to.equal(0);
+    });
+  });
+
+  describe("delegate_stake", () => {
+    it("prices new delegation shares at the slashed delegated stake", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const delegators = [await fundedKeypair(), await fundedKeypair()];
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+
+      const delegate = (delegator: web3.Keypair, amount: number) =>
+        delegateStake(p, delegator, resource, amount);
+      const first = await delegate(delegators[0], 1_000);
+
+      // Expiry slashes 5% of the 2_000 stake, half of it from the delegators.
+      const deadline = (await chainTime()) + 4;
+      const job = await openJob(p, client, clientFlux, 1_000, [resource], {
+        deadline,
+      });
+      await waitPast(deadline);
+      await expireJob(p, job, clientFlux, clientFlux, [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ]);
+
+      // 950 now buys as many shares as the first delegator's 1_000 did.
+      const second = await delegate(delegators[1], 950);
+      expect(await readU64(first, 64)).to.equal(1_000);
+      expect(await readU64(second, 64)).to.equal(1_000);
+      expect(await balance(stakePda(resource))).to.equal(2_000 - 100 + 950);
+    });
+  });
+
+  describe("claim_rewards", () => {
+    it("pays a staked host the epoch's stake emission", async () => {
+      const p = await setupProtocol();
+      const rewardsVault = pda("rewards_vault");
+      const walletFlux = await createTokenAccount(p.flux, wallet, 1_000);
+      // Two-second epochs emitting 1_000 FLUX, all of it for stake.
+      await send([
+        ix(
+          59, // initialize_rewards
+          [
+            w(wallet, true),
+            r(configPda),
+            w(rewardPoolPda),
+            r(SYSTEM_PROGRAM_ID),
+            w(rewardsVault),
+            r(TOKEN_PROGRAM_ID),
+            r(p.flux.address),
+          ],
+          i64(2),
+          u64(1_000),
+          u16(0)
+        ),
+        ix(
+          60, // fund_rewards
+          [
+            w(wallet, true),
+            w(rewardsVault),
+            w(walletFlux),
+            r(TOKEN_PROGRAM_ID),
+            r(p.flux.address),
+          ],
+          u64(1_000)
+        ),
+      ]);
+
+      // Resources staked before the pool existed are not weighted, so this host
+      // holds all of the pool's stake.
+      const host = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+
+      await waitPast((await chainTime()) + 2);
+      await send([
+        ix(
+          61, // advance_epoch
+          [w(wallet, true), w(rewardPoolPda)]
+        ),
+      ]);
+      await send(
+        [
+          ix(
+            63, // claim_rewards
+            [
+              w(host.publicKey, true),
+              w(resource),
+              w(rewardPoolPda),
+              w(hostFlux),
+              w(stakePda(resource)),
+              r(TOKEN_PROGRAM_ID),
+              w(rewardsVault),
+              r(p.flux.address),
+            ]
+          ),
+        ],
+        [host]
+      );
+
+      expect(await balance(hostFlux)).to.equal(1_000);
+      expect(await balance(rewardsVault)).to.equal(0);
+    });
+
+    it("lets anyone compound the delegators' part of the rewards", async () => {
+      // Runs after the test above, which created the reward pool.
+      const p = await setupProtocol();
+      const rewardsVault = pda("rewards_vault");
+      const walletFlux = await createTokenAccount(p.flux, wallet, 1_000);
+      await send([
+        ix(
+          60, // fund_rewards
+          [
+            w(wallet, true),
+            w(rewardsVault),
+            w(walletFlux),
+            r(TOKEN_PROGRAM_ID),
+            r(p.flux.address),
+          ],
+          u64(1_000)
+        ),
+      ]);
+
+      // Half of the resource's 2_000 stake is delegated.
+      const host = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+      await delegateStake(p, await fundedKeypair(), resource, 1_000);
+
+      await waitPast((await chainTime()) + 2);
+      await send([
+        ix(
+          61, // advance_epoch
+          [w(wallet, true), w(rewardPoolPda)]
+        ),
+      ]);
+      await send([
+        ix(
+          64, // compound_rewards
+          [
+            w(wallet, true),
+            w(resource),
+            w(rewardPoolPda),
+            w(stakePda(resource)),
+            r(TOKEN_PROGRAM_ID),
+            w(rewardsVault),
+            r(p.flux.address),
+          ]
+        ),
+      ]);
+      const compounded = (await balance(stakePda(resource))) - 2_000;
+      expect(compounded).to.be.greaterThan(0);
+
+      // The host's claim pays the other half, without compounding again.
+      await send(
+        [
+          ix(
+            63, // claim_rewards
+            [
+              w(host.publicKey, true),
+              w(resource),
+              w(rewardPoolPda),
+              w(hostFlux),
+              w(stakePda(resource)),
+              r(TOKEN_PROGRAM_ID),
+              w(rewardsVault),
+              r(p.flux.address),
+            ]
+          ),
+        ],
+        [host]
+      );
+      expect(await balance(hostFlux)).to.be.closeTo(compounded, 1);
+      expect(await balance(stakePda(resource))).to.equal(2_000 + compounded);
+    });
+  });
+
+  describe("claim_insurance", () => {
+    it("covers half of a slashed host's payment, once", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 10_000);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 10_000);
+      // Top up the pool so the payout is not capped at its balance.
+      await send([mintToIx(p.flux, insurancePda, 1_000)]);
+
+      const deadline = (await chainTime()) + 4;
+      const job = await openJob(p, client, clientFlux, 1_000, [resource], {
+        deadline,
+      });
+      await waitPast(deadline);
+      await expireJob(p, job, clientFlux, clientFlux, [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ]);
+      const refunded = await balance(clientFlux);
+
+      await claimInsurance(p, client, job, clientFlux);
+      expect((await balance(clientFlux)) - refunded).to.equal(500);
+      await expectError(claimInsurance(p, client, job, clientFlux), 52); // InsuranceClaimed
+    });
+
+    it("caps the cover at the stake slashed from the hosts", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+      await send([mintToIx(p.flux, insurancePda, 1_000)]);
+
+      const deadline = (await chainTime()) + 4;
+      const job = await openJob(p, client, clientFlux, 1_000, [resource], {
+        deadline,
+      });
+      await waitPast(deadline);
+      await expireJob(p, job, clientFlux, clientFlux, [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ]);
+      const refunded = await balance(clientFlux);
+
+      // Half of the 1_000 payment would be 500, but only 50 was slashed.
+      await claimInsurance(p, client, job, clientFlux);
+      expect((await balance(clientFlux)) - refunded).to.equal(50);
+    });
+  });
+
+  describe("set_mint_price", () => {
+    it("settles a job in a second mint at the host's quote", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostUsdc = await createTokenAccount(p.usdc, host.publicKey);
+      const clientUsdc = await createTokenAccount(p.usdc, client.publicKey, 2_000);
+      const resource = await registerResource(host);
+
+      const setMintPrice = (mint: Mint, price: number) =>
+        send(
+          [
+            ix(
+              67, // set_mint_price
+              [w(host.publicKey, true), w(resource), r(configPda)],
+              mint.address.toBuffer(),
+              u64(price)
+            ),
+          ],
+          [host]
+        );
+      await expectError(setMintPrice(p.flux, 2_000), 53); // MintNotAllowed
+      await setMintPrice(p.usdc, 2_000);
+
+      const job = await openJob(p, client, clientUsdc, 2_000, [resource], {
+        mint: p.usdc,
+        deadline: (await chainTime()) + 600,
+      });
+      const result = Buffer.alloc(32, 7);
+      const salt = Buffer.alloc(32, 1);
+      await commitResult(host, job, result, salt);
+      await revealResult(host, job, result, salt);
+      await sampleAudit(job);
+
+      const treasuryBefore = await balance(p.usdcTreasury);
+      const insuranceBefore = await balance(insurancePda);
+      await settleJob(job, clientUsdc, p.usdcTreasury, [
+        { host: host.publicKey, resource, tokenAccount: hostUsdc },
+      ]);
+
+      // The whole fee goes to the mint's treasury; only FLUX fees are insured.
+      expect(await balance(hostUsdc)).to.equal(1_900);
+      expect((await balance(p.usdcTreasury)) - treasuryBefore).to.equal(100);
+      expect(await balance(insurancePda)).to.equal(insuranceBefore);
+      expect(await balance(clientUsdc)).to.equal(0);
+    });
+  });
+
+  describe("deposit_escrow", () => {
+    it("credits a Token-2022 escrow with the amount received after fees", async () => {
+      const p = await setupProtocol();
+      const client = await fundedKeypair();
+      const clientToken = await createTokenAccount(
+        p.feeToken,
+        client.publicKey,
+        10_000
+      );
+      const job = jobAccounts(client, 1, p.feeToken);
+      await depositEscrow(job, clientToken, 10_000);
+
+      // The mint withholds 1% of the transfer.
+      expect(await balance(clientToken)).to.equal(0);
+      expect(await balance(job.vault)).to.equal(9_900);
+      expect(await readU64(job.escrow, 72)).to.equal(9_900); // amount
+    });
+  });
+
+  describe("set_usd_price", () => {
+    it("converts a USD price at the oracle price of the job's mint", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientUsdc = await createTokenAccount(
+        p.usdc,
+        client.publicKey,
+        5_999_999
+      );
+      const resource = await registerResource(host);
+      const feed = priceFeedPda(p.usdc.address);
+
+      // One whole token costs $2.00, with a 1 cent confidence interval.
+      await send([
+        ix(
+          68, // init_price_feed
+          [
+            w(wallet, true),
+            r(configPda),
+            w(feed),
+            r(p.usdc.address),
+            r(SYSTEM_PROGRAM_ID),
+          ],
+          wallet.toBuffer()
+        ),
+        ix(
+          69, // update_price_feed
+          [w(wallet, true), w(feed)],
+          u64(200),
+          u64(1),
+          i32(-2)
+        ),
+      ]);
+      // $3.00 an hour.
+      await send(
+        [
+          ix(
+            70, // set_usd_price
+            [w(host.publicKey, true), w(resource)],
+            u64(3_000_000)
+          ),
+        ],
+        [host]
+      );
+
+      // Two hours at $3.00 cost 3 whole tokens.
+      const opts = {
+        mint: p.usdc,
+        deadline: (await chainTime()) + 600,
+        requirements: { durationHours: 2 },
+      };
+      await expectError(
+        openJob(p, client, clientUsdc, 2_999_999, [resource], { ...opts, id: 1 }),
+        5 // InsufficientFunds
+      );
+      const job = await openJob(p, client, clientUsdc, 3_000_000, [resource], {
+        ...opts,
+        id: 2,
+      });
+      expect(await balance(job.vault)).to.equal(3_000_000);
+    });
+  });
+
+  describe("escrow vault", () => {
+    it("is a token account of the escrow's mint owned by the escrow", async () => {
+      const p = await setupProtocol();
+      const client = await fundedKeypair();
+      for (const mint of [p.flux, p.feeToken]) {
+        const clientToken = await createTokenAccount(mint, client.publicKey, 100);
+        const job = jobAccounts(client, mint === p.flux ? 1 : 2, mint);
+        await depositEscrow(job, clientToken, 100);
+
+        const vault = await connection.getAccountInfo(job.vault);
+        expect(vault.owner.equals(mint.tokenProgram)).to.be.true;
+        expect(vault.data.length).to.equal(mint.accountSize);
+        const data = Buffer.from(vault.data);
+        expect(data.subarray(0, 32).equals(mint.address.toBuffer())).to.be.true;
+        expect(data.subarray(32, 64).equals(job.escrow.toBuffer())).to.be.true;
+      }
+    });
+  });
+
+  describe("close_job", () => {
+    it("keeps running jobs and recently failed jobs open", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const hostFlux = await createTokenAccount(p.flux, host.publicKey, 1_000);
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+      await stakeFlux(p, host, resource, hostFlux, 1_000);
+
+      const deadline = (await chainTime()) + 4;
+      const job = await openJob(p, client, clientFlux, 1_000, [resource], {
+        deadline,
+      });
+      const closeJob = () =>
+        send(
+          [
+            ix(
+              71, // close_job
+              [
+                w(client.publicKey, true),
+                w(job.address),
+                w(job.escrow),
+                w(job.vault),
+                r(TOKEN_PROGRAM_ID),
+                w(p.flux.address),
+                w(auctionPda(job)),
+                w(checkpointPda(job.address, host.publicKey)),
+                w(host.publicKey),
+              ]
+            ),
+          ],
+          [client]
+        );
+      await expectError(closeJob(), 4); // InvalidJobStatus
+
+      await waitPast(deadline);
+      await expireJob(p, job, clientFlux, clientFlux, [
+        { host: host.publicKey, resource, tokenAccount: hostFlux },
+      ]);
+      await expectError(closeJob(), 58); // DisputeWindowOpen
+    });
+  });
+
+  describe("account rent", () => {
+    it("funds new accounts at the rent-exempt minimum, even if prefunded", async () => {
+      const p = await setupProtocol();
+      const host = await fundedKeypair();
+      const client = await fundedKeypair();
+      const clientFlux = await createTokenAccount(p.flux, client.publicKey, 1_000);
+      const resource = await registerResource(host);
+
+      // Anyone can send lamports to the escrow address before it exists.
+      const job = jobAccounts(client, 1, p.flux);
+      await send([
+        web3.SystemProgram.transfer({
+          fromPubkey: wallet,
+          toPubkey: job.escrow,
+          lamports: 1_000,
+        }),
+      ]);
+      await depositEscrow(job, clientFlux, 1_000);
+      await startJob(client, job, [resource], {
+        deadline: (await chainTime()) + 600,
+      });
+
+      const escrow = await connection.getAccountInfo(job.escrow);
+      expect(escrow.owner.equals(PROGRAM_ID)).to.be.true;
+      expect(escrow.data.length).to.equal(ESCROW_ACCOUNT_SPACE);
+      expect(escrow.lamports).to.equal(
+        await connection.getMinimumBalanceForRentExemption(ESCROW_ACCOUNT_SPACE)
+      );
+      expect(await connection.getBalance(job.address)).to.equal(
+        await connection.getMinimumBalanceForRentExemption(JOB_ACCOUNT_SPACE)
+      );
+    });
+  });
 });